@@ -0,0 +1,51 @@
+use aoc_core::scan::Numbers;
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use std::io::BufReader;
+
+const DEPTHS: usize = 2_000_000;
+
+fn depth_log() -> Vec<u8> {
+    let mut depth: usize = 150;
+    let mut seed: u64 = 0x2021;
+    let mut log = Vec::new();
+
+    for _ in 0..DEPTHS {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        depth = (depth + (seed >> 60) as usize).saturating_sub(7);
+        log.extend_from_slice(depth.to_string().as_bytes());
+        log.push(b'\n');
+    }
+
+    log
+}
+
+fn bench_scan(c: &mut Criterion) {
+    let log = depth_log();
+    let mut group = c.benchmark_group("depth log");
+    group.throughput(Throughput::Bytes(log.len() as u64));
+
+    group.bench_function("Numbers::lines", |b| {
+        b.iter(|| {
+            Numbers::lines(BufReader::with_capacity(64 * 1024, black_box(&log[..])))
+                .map(|depth| depth.unwrap())
+                .fold(0usize, usize::wrapping_add)
+        })
+    });
+
+    group.bench_function("str::lines + parse", |b| {
+        b.iter(|| {
+            std::str::from_utf8(black_box(&log))
+                .unwrap()
+                .lines()
+                .map(|line| line.parse::<usize>().unwrap())
+                .fold(0usize, usize::wrapping_add)
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_scan);
+criterion_main!(benches);
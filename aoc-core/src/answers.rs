@@ -0,0 +1,111 @@
+use crate::solver::Answer;
+use anyhow::Result;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Expected {
+    Number(i64),
+    Text(String),
+}
+
+impl Expected {
+    fn as_string(&self) -> String {
+        match self {
+            Expected::Number(value) => value.to_string(),
+            Expected::Text(value) => value.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Entry {
+    day: u8,
+    part: u8,
+    input: String,
+    expected: Expected,
+}
+
+/// Known-good answers, keyed by day, part and the SHA-256 of the puzzle input.
+///
+/// ```toml
+/// [[answer]]
+/// day = 1
+/// part = 1
+/// input = "5b1f...e0"
+/// expected = 1154
+/// ```
+#[derive(Debug, Default, Deserialize)]
+pub struct Answers {
+    #[serde(default, rename = "answer")]
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl Answers {
+    pub fn parse(input: &str) -> Result<Self> {
+        Ok(toml::from_str(input)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn check(&self, day: u8, part: u8, input_hash: &str, answer: &Answer) -> Verdict {
+        let entry = self.entries.iter().find(|entry| {
+            entry.day == day && entry.part == part && entry.input.eq_ignore_ascii_case(input_hash)
+        });
+
+        match entry {
+            Some(entry) if entry.expected.as_string() == answer.to_string() => Verdict::Pass,
+            Some(entry) => Verdict::Fail {
+                expected: entry.expected.as_string(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+pub fn input_hash(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let hash = input_hash("1\n2\n");
+        let answers = Answers::parse(&format!(
+            "[[answer]]\nday = 1\npart = 1\ninput = \"{}\"\nexpected = 1\n\n\
+             [[answer]]\nday = 1\npart = 2\ninput = \"{}\"\nexpected = \"3\"\n",
+            hash, hash
+        ))
+        .unwrap();
+
+        assert_eq!(
+            answers.check(1, 1, &hash, &Answer::Number(1)),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(1, 2, &hash, &Answer::Number(0)),
+            Verdict::Fail {
+                expected: "3".to_string()
+            }
+        );
+        assert_eq!(
+            answers.check(2, 1, &hash, &Answer::Number(1)),
+            Verdict::Unknown
+        );
+    }
+}
@@ -0,0 +1,149 @@
+//! Arithmetic that can be switched to checked mode.
+//!
+//! By default these helpers behave like the plain operators (wrapping in
+//! release builds, panicking in debug builds). Once `set_checked(true)` has
+//! been called on a thread, any overflow there is returned as an
+//! `OverflowError` naming the day and the operation instead.
+
+use std::cell::Cell;
+use std::fmt;
+
+thread_local! {
+    static CHECKED: Cell<bool> = const { Cell::new(false) };
+}
+
+pub fn set_checked(checked: bool) {
+    CHECKED.with(|cell| cell.set(checked));
+}
+
+pub fn is_checked() -> bool {
+    CHECKED.with(Cell::get)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverflowError {
+    pub day: u8,
+    pub operation: &'static str,
+}
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {}: arithmetic overflow in {}",
+            self.day, self.operation
+        )
+    }
+}
+
+impl std::error::Error for OverflowError {}
+
+pub trait Integer: Copy {
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn add(self, rhs: Self) -> Self;
+    fn sub(self, rhs: Self) -> Self;
+    fn mul(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn add(self, rhs: Self) -> Self {
+                    self + rhs
+                }
+
+                fn sub(self, rhs: Self) -> Self {
+                    self - rhs
+                }
+
+                fn mul(self, rhs: Self) -> Self {
+                    self * rhs
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(usize, isize, u64, i64);
+
+fn apply<T>(
+    day: u8,
+    operation: &'static str,
+    checked: impl FnOnce() -> Option<T>,
+    plain: impl FnOnce() -> T,
+) -> Result<T, OverflowError> {
+    if is_checked() {
+        checked().ok_or(OverflowError { day, operation })
+    } else {
+        Ok(plain())
+    }
+}
+
+pub fn add<T: Integer>(day: u8, operation: &'static str, a: T, b: T) -> Result<T, OverflowError> {
+    apply(day, operation, || a.checked_add(b), || a.add(b))
+}
+
+pub fn sub<T: Integer>(day: u8, operation: &'static str, a: T, b: T) -> Result<T, OverflowError> {
+    apply(day, operation, || a.checked_sub(b), || a.sub(b))
+}
+
+pub fn mul<T: Integer>(day: u8, operation: &'static str, a: T, b: T) -> Result<T, OverflowError> {
+    apply(day, operation, || a.checked_mul(b), || a.mul(b))
+}
+
+pub fn sum<T: Integer>(
+    day: u8,
+    operation: &'static str,
+    zero: T,
+    values: impl IntoIterator<Item = T>,
+) -> Result<T, OverflowError> {
+    values
+        .into_iter()
+        .try_fold(zero, |acc, value| add(day, operation, acc, value))
+}
+
+pub fn to_isize(day: u8, operation: &'static str, value: usize) -> Result<isize, OverflowError> {
+    apply(
+        day,
+        operation,
+        || isize::try_from(value).ok(),
+        || value as isize,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_mode() {
+        set_checked(true);
+
+        assert_eq!(
+            sub(2, "depth after 'up'", 1usize, 2)
+                .unwrap_err()
+                .to_string(),
+            "day 2: arithmetic overflow in depth after 'up'"
+        );
+        assert_eq!(add(6, "fish count", 1usize, 2), Ok(3));
+        assert!(to_isize(7, "crab position", usize::MAX).is_err());
+
+        set_checked(false);
+        assert_eq!(to_isize(7, "crab position", usize::MAX), Ok(-1));
+    }
+}
@@ -0,0 +1,145 @@
+//! Fixed-width bit strings packed into `u64` words.
+
+use num_bigint::BigUint;
+use std::fmt;
+
+/// A bit string of any width. Position 0 is the leftmost, most significant
+/// bit, matching how diagnostic numbers are written.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Bits {
+    width: usize,
+    // Little-endian words, so the last position is bit 0 of the first word.
+    words: Vec<u64>,
+}
+
+impl Bits {
+    pub fn zeros(width: usize) -> Self {
+        Bits {
+            width,
+            words: vec![0; width.div_ceil(64)],
+        }
+    }
+
+    /// Builds a bit string from `'0'` and `'1'` characters, or returns the
+    /// byte offset of the first other character.
+    pub fn from_binary(s: &str) -> Result<Self, usize> {
+        let mut bits = Bits::zeros(s.len());
+
+        for (pos, c) in s.bytes().enumerate() {
+            match c {
+                b'0' => {}
+                b'1' => bits.set(pos, true),
+                _ => return Err(pos),
+            }
+        }
+
+        Ok(bits)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    fn index(&self, pos: usize) -> (usize, u32) {
+        assert!(pos < self.width, "bit {} out of {}", pos, self.width);
+        let bit = self.width - 1 - pos;
+        (bit / 64, (bit % 64) as u32)
+    }
+
+    pub fn get(&self, pos: usize) -> bool {
+        let (word, bit) = self.index(pos);
+        self.words[word] >> bit & 1 == 1
+    }
+
+    pub fn set(&mut self, pos: usize, value: bool) {
+        let (word, bit) = self.index(pos);
+
+        if value {
+            self.words[word] |= 1 << bit;
+        } else {
+            self.words[word] &= !(1 << bit);
+        }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Positions of the set bits, leftmost first.
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .rev()
+            .flat_map(move |(i, &word)| {
+                let mut word = word;
+                std::iter::from_fn(move || {
+                    if word == 0 {
+                        return None;
+                    }
+
+                    let bit = 63 - word.leading_zeros() as usize;
+                    word &= !(1 << bit);
+                    Some(self.width - 1 - (i * 64 + bit))
+                })
+            })
+    }
+
+    /// Flips every bit within the width.
+    pub fn complement(&self) -> Self {
+        let mut words: Vec<u64> = self.words.iter().map(|word| !word).collect();
+
+        if let Some(last) = words.last_mut() {
+            let used = self.width % 64;
+            if used != 0 {
+                *last &= (1 << used) - 1;
+            }
+        }
+
+        Bits {
+            width: self.width,
+            words,
+        }
+    }
+
+    pub fn to_biguint(&self) -> BigUint {
+        let bytes: Vec<u8> = self
+            .words
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect();
+        BigUint::from_bytes_le(&bytes)
+    }
+}
+
+impl fmt::Display for Bits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for pos in 0..self.width {
+            f.write_str(if self.get(pos) { "1" } else { "0" })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wide_bits() {
+        let binary = format!("1{}01", "0".repeat(70));
+        let bits = Bits::from_binary(&binary).unwrap();
+
+        assert_eq!(bits.width(), 73);
+        assert_eq!(bits.to_string(), binary);
+        assert_eq!(bits.ones().collect::<Vec<_>>(), vec![0, 72]);
+        assert_eq!(bits.count_ones(), 2);
+        assert_eq!(bits.to_biguint(), (BigUint::from(1u8) << 72) + 1u8);
+        assert_eq!(bits.complement().count_ones(), 71);
+        assert_eq!(Bits::from_binary("01x"), Err(2));
+    }
+}
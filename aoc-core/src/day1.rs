@@ -0,0 +1,137 @@
+use crate::scan::Numbers;
+use crate::solver::{Answer, Solver};
+use crate::window::{self, Aggregate};
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::BufRead;
+
+pub fn parse_depths(input: &str) -> Result<Vec<usize>> {
+    Numbers::lines(input.as_bytes()).collect()
+}
+
+/// Streams depths out of `reader` in constant memory.
+pub fn read_depths<R: BufRead>(reader: R) -> Numbers<R> {
+    Numbers::lines(reader)
+}
+
+/// Statistics over a depth log, accumulated one measurement at a time.
+#[derive(Debug, Default, Serialize)]
+pub struct DepthReport {
+    pub measurements: usize,
+    pub increases: usize,
+    pub decreases: usize,
+    pub plateaus: usize,
+    pub longest_increasing_run: usize,
+    pub max_drop: usize,
+    /// Count of depth changes, keyed by the lower bound of their bucket.
+    pub deltas: BTreeMap<isize, usize>,
+    #[serde(skip)]
+    bucket_width: usize,
+    #[serde(skip)]
+    previous_value: Option<usize>,
+    #[serde(skip)]
+    current_run: usize,
+}
+
+impl DepthReport {
+    pub fn new(bucket_width: usize) -> Self {
+        Self {
+            bucket_width: bucket_width.max(1),
+            ..Default::default()
+        }
+    }
+
+    pub fn push(&mut self, value: usize) {
+        self.measurements += 1;
+
+        match self.previous_value {
+            Some(previous_value) => {
+                let delta = value as isize - previous_value as isize;
+                let width = self.bucket_width as isize;
+                *self
+                    .deltas
+                    .entry(delta.div_euclid(width) * width)
+                    .or_insert(0) += 1;
+
+                if value > previous_value {
+                    self.increases += 1;
+                    self.current_run += 1;
+                } else {
+                    if value < previous_value {
+                        self.decreases += 1;
+                        self.max_drop = self.max_drop.max(previous_value - value);
+                    } else {
+                        self.plateaus += 1;
+                    }
+                    self.current_run = 1;
+                }
+            }
+            None => self.current_run = 1,
+        }
+
+        self.longest_increasing_run = self.longest_increasing_run.max(self.current_run);
+        self.previous_value = Some(value);
+    }
+}
+
+pub fn part1(depths: &[usize]) -> usize {
+    window::increases(depths.iter().copied(), 1, Aggregate::Sum)
+}
+
+pub fn part2(depths: &[usize]) -> usize {
+    window::increases(depths.iter().copied(), 3, Aggregate::Sum)
+}
+
+pub struct SonarSweep;
+
+impl Solver for SonarSweep {
+    type Input = Vec<usize>;
+
+    const DAY: u8 = 1;
+    const NAME: &'static str = "Sonar Sweep";
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_depths(input)
+    }
+
+    fn part1(&self, depths: &Self::Input) -> Result<Answer> {
+        Ok(part1(depths).into())
+    }
+
+    fn part2(&self, depths: &Self::Input) -> Result<Answer> {
+        Ok(part2(depths).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::assert_example;
+
+    #[test]
+    fn test_example() {
+        assert_example(
+            SonarSweep,
+            include_str!("../fixtures/day1.txt"),
+            Answer::Number(7),
+            Answer::Number(5),
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let mut report = DepthReport::new(5);
+
+        for depth in read_depths(include_str!("../fixtures/day1.txt").as_bytes()) {
+            report.push(depth.unwrap());
+        }
+
+        assert_eq!(report.increases, 7);
+        assert_eq!(report.decreases, 2);
+        assert_eq!(report.plateaus, 0);
+        assert_eq!(report.longest_increasing_run, 4);
+        assert_eq!(report.max_drop, 10);
+        assert_eq!(report.deltas[&-10], 2);
+    }
+}
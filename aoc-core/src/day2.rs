@@ -0,0 +1,354 @@
+use crate::arith::{self, OverflowError};
+use crate::parse::{self, ParseError, Parsed};
+use crate::solver::{Answer, Solver};
+use anyhow::Result;
+use nom::{
+    branch, bytes::complete as bytes, character::complete as character, combinator, sequence,
+};
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+pub mod script;
+
+const DAY: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Move {
+    Forward(usize),
+    Back(usize),
+    Up(usize),
+    Down(usize),
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Move::Forward(amount) => write!(f, "forward {}", amount),
+            Move::Back(amount) => write!(f, "back {}", amount),
+            Move::Up(amount) => write!(f, "up {}", amount),
+            Move::Down(amount) => write!(f, "down {}", amount),
+        }
+    }
+}
+
+impl Serialize for Move {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl FromStr for Move {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        parse::finish(
+            s,
+            sequence::terminated(
+                Move::parse,
+                parse::context("end of line", parse::end_of_line),
+            )(s),
+        )
+    }
+}
+
+impl Move {
+    pub fn parse(input: &str) -> Parsed<'_, Self> {
+        let command = parse::context(
+            "'forward', 'back', 'up' or 'down'",
+            branch::alt((
+                bytes::tag("forward"),
+                bytes::tag("back"),
+                bytes::tag("up"),
+                bytes::tag("down"),
+            )),
+        );
+        let amount = sequence::preceded(
+            parse::context("' ' after command", character::space1),
+            parse::context("an amount", parse::number),
+        );
+
+        parse::context(
+            "dive command",
+            combinator::map(
+                sequence::pair(command, amount),
+                |(command, amount)| match command {
+                    "forward" => Move::Forward(amount),
+                    "back" => Move::Back(amount),
+                    "up" => Move::Up(amount),
+                    _ => Move::Down(amount),
+                },
+            ),
+        )(input)
+    }
+}
+
+/// A move along with the script line it came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub line: usize,
+    pub command: Move,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Submarine {
+    pub horizontal: isize,
+    pub depth: isize,
+    pub aim: isize,
+}
+
+/// How a command moves the submarine, so new steering rules can reuse the
+/// same command language.
+pub trait Model {
+    fn apply(&self, sub: &mut Submarine, command: &Move) -> Result<(), OverflowError>;
+}
+
+/// `up` and `down` change the depth directly.
+pub struct Plain;
+
+impl Model for Plain {
+    fn apply(&self, sub: &mut Submarine, command: &Move) -> Result<(), OverflowError> {
+        match *command {
+            Move::Forward(amount) => {
+                let amount = arith::to_isize(DAY, "forward amount", amount)?;
+                sub.horizontal =
+                    arith::add(DAY, "horizontal after 'forward'", sub.horizontal, amount)?;
+            }
+            Move::Back(amount) => {
+                let amount = arith::to_isize(DAY, "back amount", amount)?;
+                sub.horizontal =
+                    arith::sub(DAY, "horizontal after 'back'", sub.horizontal, amount)?;
+            }
+            Move::Up(amount) => {
+                let amount = arith::to_isize(DAY, "up amount", amount)?;
+                sub.depth = arith::sub(DAY, "depth after 'up'", sub.depth, amount)?;
+            }
+            Move::Down(amount) => {
+                let amount = arith::to_isize(DAY, "down amount", amount)?;
+                sub.depth = arith::add(DAY, "depth after 'down'", sub.depth, amount)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// `up` and `down` change the aim, and `forward` and `back` travel along it.
+pub struct Aimed;
+
+impl Model for Aimed {
+    fn apply(&self, sub: &mut Submarine, command: &Move) -> Result<(), OverflowError> {
+        match *command {
+            Move::Forward(amount) => {
+                let amount = arith::to_isize(DAY, "forward amount", amount)?;
+                let dive = arith::mul(DAY, "dive along the aim", sub.aim, amount)?;
+                sub.horizontal =
+                    arith::add(DAY, "horizontal after 'forward'", sub.horizontal, amount)?;
+                sub.depth = arith::add(DAY, "depth after 'forward'", sub.depth, dive)?;
+            }
+            Move::Back(amount) => {
+                let amount = arith::to_isize(DAY, "back amount", amount)?;
+                let rise = arith::mul(DAY, "rise along the aim", sub.aim, amount)?;
+                sub.horizontal =
+                    arith::sub(DAY, "horizontal after 'back'", sub.horizontal, amount)?;
+                sub.depth = arith::sub(DAY, "depth after 'back'", sub.depth, rise)?;
+            }
+            Move::Up(amount) => {
+                let amount = arith::to_isize(DAY, "up amount", amount)?;
+                sub.aim = arith::sub(DAY, "aim after 'up'", sub.aim, amount)?;
+            }
+            Move::Down(amount) => {
+                let amount = arith::to_isize(DAY, "down amount", amount)?;
+                sub.aim = arith::add(DAY, "aim after 'down'", sub.aim, amount)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Submarine state right after a step.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Waypoint {
+    pub line: usize,
+    pub command: Move,
+    pub horizontal: isize,
+    pub depth: isize,
+    pub aim: isize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SurfaceBreach {
+    pub line: usize,
+    pub command: Move,
+    pub depth: isize,
+}
+
+impl fmt::Display for SurfaceBreach {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: '{}' takes the submarine {} above the surface",
+            self.line, self.command, -self.depth
+        )
+    }
+}
+
+impl std::error::Error for SurfaceBreach {}
+
+/// Waypoints whose command drove the submarine above the surface, or
+/// further up while already above it.
+pub fn surface_breaches(waypoints: &[Waypoint]) -> Vec<SurfaceBreach> {
+    let mut depth = 0;
+
+    waypoints
+        .iter()
+        .filter_map(|waypoint| {
+            let rose = waypoint.depth < 0 && waypoint.depth < depth;
+            depth = waypoint.depth;
+
+            rose.then_some(SurfaceBreach {
+                line: waypoint.line,
+                command: waypoint.command,
+                depth: waypoint.depth,
+            })
+        })
+        .collect()
+}
+
+impl Submarine {
+    /// Runs every step, stopping at the first one that surfaces the submarine.
+    pub fn execute(&mut self, model: &dyn Model, steps: &[Step]) -> Result<()> {
+        for step in steps {
+            model.apply(self, &step.command)?;
+
+            if self.depth < 0 {
+                return Err(SurfaceBreach {
+                    line: step.line,
+                    command: step.command,
+                    depth: self.depth,
+                }
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs every step, recording where the submarine is after each one.
+    pub fn trace(
+        &mut self,
+        model: &dyn Model,
+        steps: &[Step],
+    ) -> Result<Vec<Waypoint>, OverflowError> {
+        steps
+            .iter()
+            .map(|step| {
+                model.apply(self, &step.command)?;
+
+                Ok(Waypoint {
+                    line: step.line,
+                    command: step.command,
+                    horizontal: self.horizontal,
+                    depth: self.depth,
+                    aim: self.aim,
+                })
+            })
+            .collect()
+    }
+
+    pub fn position_product(&self) -> Result<isize, OverflowError> {
+        arith::mul(DAY, "horizontal times depth", self.horizontal, self.depth)
+    }
+}
+
+/// Parses the puzzle input, which is also a valid submarine script.
+pub fn parse_moves(input: &str) -> Result<Vec<Step>, ParseError> {
+    script::compile(input)
+}
+
+pub fn navigate(model: &dyn Model, steps: &[Step]) -> Result<Submarine> {
+    let mut sub = Submarine::default();
+    sub.execute(model, steps)?;
+    Ok(sub)
+}
+
+pub fn part1(steps: &[Step]) -> Result<isize> {
+    Ok(navigate(&Plain, steps)?.position_product()?)
+}
+
+pub fn part2(steps: &[Step]) -> Result<isize> {
+    Ok(navigate(&Aimed, steps)?.position_product()?)
+}
+
+pub struct Dive;
+
+impl Solver for Dive {
+    type Input = Vec<Step>;
+
+    const DAY: u8 = DAY;
+    const NAME: &'static str = "Dive!";
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Ok(parse_moves(input)?)
+    }
+
+    fn part1(&self, steps: &Self::Input) -> Result<Answer> {
+        Ok(part1(steps)?.into())
+    }
+
+    fn part2(&self, steps: &Self::Input) -> Result<Answer> {
+        Ok(part2(steps)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::assert_example;
+
+    #[test]
+    fn test_example() {
+        assert_example(
+            Dive,
+            include_str!("../fixtures/day2.txt"),
+            Answer::Number(150),
+            Answer::Number(900),
+        );
+    }
+
+    #[test]
+    fn test_surface_breach() {
+        let steps = parse_moves("down 2\nforward 1\nup 3\n").unwrap();
+
+        assert_eq!(
+            part1(&steps).unwrap_err().to_string(),
+            "line 3: 'up 3' takes the submarine 1 above the surface"
+        );
+        assert!(part2(&steps).is_ok());
+    }
+
+    #[test]
+    fn test_trace_breaches() {
+        let steps = parse_moves(
+            "macro dip {\n  down 5\n  up 9\n}\nforward 2\ndip\nforward 1\nup 1\ndown 2\n",
+        )
+        .unwrap();
+        let waypoints = Submarine::default().trace(&Plain, &steps).unwrap();
+
+        assert_eq!(
+            surface_breaches(&waypoints)
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            [
+                "line 6: 'up 9' takes the submarine 4 above the surface",
+                "line 8: 'up 1' takes the submarine 5 above the surface",
+            ]
+        );
+        assert_eq!(
+            part1(&steps).unwrap_err().to_string(),
+            "line 6: 'up 9' takes the submarine 4 above the surface"
+        );
+    }
+}
@@ -0,0 +1,319 @@
+//! Submarine navigation scripts.
+//!
+//! ```text
+//! # dive under the reef, then come back
+//! macro zigzag {
+//!     down 2 forward 3
+//!     up 2 forward 3
+//! }
+//!
+//! repeat 4 { zigzag }
+//! turn        # forward and back swap meaning from here on
+//! forward 24
+//! ```
+//!
+//! Besides `forward`, `back`, `up` and `down`, a script may `turn` around,
+//! `repeat N { ... }` a block, define a `macro name { ... }` and call it by
+//! name. Everything after `#` on a line is a comment. Scripts compile down
+//! to a plain `Move` sequence.
+
+use super::{Move, Step};
+use crate::parse::{self, LineIndex, ParseError, Parsed};
+use nom::{
+    branch, bytes::complete as bytes, character::complete as character, combinator, multi, sequence,
+};
+use std::collections::HashMap;
+
+/// Upper bound on compiled moves plus `repeat` iterations, so a runaway
+/// `repeat` fails instead of exhausting memory or spinning on an empty body.
+pub const MAX_EXPANSION: usize = 10_000_000;
+
+const KEYWORDS: [&str; 7] = ["forward", "back", "up", "down", "turn", "repeat", "macro"];
+
+#[derive(Debug, PartialEq, Eq)]
+enum Statement<'a> {
+    Move(Move, &'a str),
+    Turn,
+    Repeat(usize, Vec<Statement<'a>>, &'a str),
+    Macro(&'a str, Vec<Statement<'a>>),
+    Call(&'a str),
+}
+
+fn comment(input: &str) -> Parsed<'_, &str> {
+    combinator::recognize(sequence::pair(
+        character::char('#'),
+        character::not_line_ending,
+    ))(input)
+}
+
+fn blank(input: &str) -> Parsed<'_, ()> {
+    combinator::value(
+        (),
+        multi::many0_count(branch::alt((character::multispace1, comment))),
+    )(input)
+}
+
+fn identifier(input: &str) -> Parsed<'_, &str> {
+    combinator::recognize(sequence::pair(
+        branch::alt((character::alpha1, bytes::tag("_"))),
+        multi::many0_count(branch::alt((
+            character::alphanumeric1,
+            bytes::tag("_"),
+            bytes::tag("-"),
+        ))),
+    ))(input)
+}
+
+fn block(input: &str) -> Parsed<'_, Vec<Statement<'_>>> {
+    sequence::delimited(
+        sequence::pair(blank, parse::context("'{'", character::char('{'))),
+        statements,
+        sequence::pair(blank, parse::context("'}'", character::char('}'))),
+    )(input)
+}
+
+fn statement(input: &str) -> Parsed<'_, Statement<'_>> {
+    let (rest, word) = parse::context("a command", identifier)(input)?;
+
+    let (rest, statement) = match word {
+        "forward" | "back" | "up" | "down" => {
+            let (rest, command) = combinator::cut(Move::parse)(input)?;
+            (rest, Statement::Move(command, input))
+        }
+        "turn" => (rest, Statement::Turn),
+        "repeat" => combinator::cut(parse::context(
+            "repeat block",
+            combinator::map(
+                sequence::pair(
+                    sequence::preceded(
+                        character::space1,
+                        parse::context("a repeat count", parse::number),
+                    ),
+                    block,
+                ),
+                |(count, body)| Statement::Repeat(count, body, input),
+            ),
+        ))(rest)?,
+        "macro" => combinator::cut(parse::context(
+            "macro definition",
+            combinator::map(
+                sequence::pair(
+                    sequence::preceded(
+                        character::space1,
+                        parse::context(
+                            "a macro name",
+                            combinator::verify(identifier, |name: &str| !KEYWORDS.contains(&name)),
+                        ),
+                    ),
+                    block,
+                ),
+                |(name, body)| Statement::Macro(name, body),
+            ),
+        ))(rest)?,
+        name => (rest, Statement::Call(name)),
+    };
+
+    let (rest, _) = combinator::cut(parse::context(
+        "end of command",
+        combinator::peek(branch::alt((
+            character::multispace1,
+            bytes::tag("#"),
+            bytes::tag("}"),
+            combinator::eof,
+        ))),
+    ))(rest)?;
+
+    Ok((rest, statement))
+}
+
+fn statements(input: &str) -> Parsed<'_, Vec<Statement<'_>>> {
+    multi::many0(sequence::preceded(blank, statement))(input)
+}
+
+fn script(input: &str) -> Parsed<'_, Vec<Statement<'_>>> {
+    sequence::terminated(
+        statements,
+        sequence::pair(blank, parse::context("a command", combinator::eof)),
+    )(input)
+}
+
+struct Compiler<'a, 's> {
+    input: &'a str,
+    lines: LineIndex<'a>,
+    macros: HashMap<&'a str, &'s [Statement<'a>]>,
+    calls: Vec<&'a str>,
+    /// Line of the outermost macro call being expanded.
+    call_site: Option<usize>,
+    turned: bool,
+    steps: Vec<Step>,
+    /// Moves emitted plus `repeat` iterations run so far.
+    expanded: usize,
+}
+
+impl<'a, 's> Compiler<'a, 's> {
+    /// Counts `amount` more moves or iterations against `MAX_EXPANSION`.
+    fn expand(&mut self, at: &'a str, amount: usize) -> Result<(), ParseError> {
+        if amount > MAX_EXPANSION - self.expanded {
+            return Err(ParseError::at(
+                self.input,
+                at,
+                format!("at most {} moves and repeats once expanded", MAX_EXPANSION),
+            ));
+        }
+
+        self.expanded += amount;
+        Ok(())
+    }
+
+    fn emit(&mut self, command: Move, at: &'a str) -> Result<(), ParseError> {
+        self.expand(at, 1)?;
+
+        let command = match (command, self.turned) {
+            (Move::Forward(amount), true) => Move::Back(amount),
+            (Move::Back(amount), true) => Move::Forward(amount),
+            (command, _) => command,
+        };
+
+        self.steps.push(Step {
+            line: self.call_site.unwrap_or_else(|| self.lines.line(at)),
+            command,
+        });
+
+        Ok(())
+    }
+
+    fn compile(&mut self, statements: &'s [Statement<'a>]) -> Result<(), ParseError> {
+        for statement in statements {
+            match statement {
+                Statement::Move(command, at) => self.emit(*command, at)?,
+                Statement::Turn => self.turned = !self.turned,
+                Statement::Repeat(count, body, at) => {
+                    // Iterations count too, so bodies without moves still end.
+                    self.expand(at, *count)?;
+                    for _ in 0..*count {
+                        self.compile(body)?;
+                    }
+                }
+                Statement::Macro(name, body) => {
+                    self.macros.insert(name, body);
+                }
+                Statement::Call(name) => {
+                    let body = *self.macros.get(name).ok_or_else(|| {
+                        ParseError::at(self.input, name, "a command or defined macro")
+                    })?;
+
+                    if self.calls.contains(name) {
+                        return Err(ParseError::at(
+                            self.input,
+                            name,
+                            "a non-recursive macro call",
+                        ));
+                    }
+
+                    if self.calls.is_empty() {
+                        self.call_site = Some(self.lines.line(name));
+                    }
+                    self.calls.push(name);
+                    self.compile(body)?;
+                    self.calls.pop();
+                    if self.calls.is_empty() {
+                        self.call_site = None;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a script and expands it into the moves it performs, each tagged
+/// with the line of the command that produced it, or of the outermost macro
+/// call it came from.
+pub fn compile(input: &str) -> Result<Vec<Step>, ParseError> {
+    let statements = parse::finish(input, script(input))?;
+    let mut compiler = Compiler {
+        input,
+        lines: LineIndex::new(input),
+        macros: HashMap::new(),
+        calls: Vec::new(),
+        call_site: None,
+        turned: false,
+        steps: Vec::new(),
+        expanded: 0,
+    };
+
+    compiler.compile(&statements)?;
+
+    Ok(compiler.steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compile() {
+        let steps = compile(
+            "macro zigzag { down 2 forward 3 } # comment\n\
+             repeat 2 {\n  zigzag\n}\nturn\nforward 1\nback 4\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            steps
+                .iter()
+                .map(|step| (step.line, step.command))
+                .collect::<Vec<_>>(),
+            vec![
+                (3, Move::Down(2)),
+                (3, Move::Forward(3)),
+                (3, Move::Down(2)),
+                (3, Move::Forward(3)),
+                (6, Move::Back(1)),
+                (7, Move::Forward(4)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            compile("forward 5\nrepeat 2 { up 1\n")
+                .unwrap_err()
+                .to_string(),
+            "line 3, col 1: expected '}' in repeat block"
+        );
+        assert_eq!(
+            compile("forward 5\nsideways\n").unwrap_err().to_string(),
+            "line 2, col 1: expected a command or defined macro"
+        );
+        assert_eq!(
+            compile("macro loop { loop }\nloop\n")
+                .unwrap_err()
+                .to_string(),
+            "line 1, col 14: expected a non-recursive macro call"
+        );
+        assert_eq!(
+            compile("forward 1 up\n").unwrap_err().to_string(),
+            "line 1, col 13: expected ' ' after command in dive command"
+        );
+    }
+
+    #[test]
+    fn test_runaway_repeat() {
+        for body in ["{ }", "{ turn }", "{ macro m { } m }"] {
+            let script = format!("repeat 99999999999999 {}\nforward 1\n", body);
+            assert_eq!(
+                compile(&script).unwrap_err().to_string(),
+                format!(
+                    "line 1, col 1: expected at most {} moves and repeats once expanded",
+                    MAX_EXPANSION
+                )
+            );
+        }
+
+        let steps = compile("repeat 3 { turn }\nforward 1\n").unwrap();
+        assert_eq!(steps[0].command, Move::Back(1));
+    }
+}
@@ -0,0 +1,515 @@
+use crate::bits::Bits;
+use crate::parse::ParseError;
+use crate::solver::{Answer, Solver};
+use anyhow::{Context, Error, Result};
+use num_bigint::BigUint;
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Common {
+    Most,
+    Least,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitOrder {
+    MsbFirst,
+    LsbFirst,
+}
+
+impl BitOrder {
+    /// The bit position looked at in step `step` of a filter.
+    fn position(self, step: usize, width: usize) -> usize {
+        match self {
+            BitOrder::MsbFirst => step,
+            BitOrder::LsbFirst => width - 1 - step,
+        }
+    }
+}
+
+/// How a rating filters the report: which bit value to keep at each step,
+/// which one wins a tie, the order bits are looked at and how many numbers
+/// may survive.
+///
+/// Written as comma-separated terms, e.g. `least,tie=1,lsb,keep=2`. Omitted
+/// terms default to the puzzle rules: `most`, a tie keeping 1 for `most` and
+/// 0 for `least`, `msb` and `keep=1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Criteria {
+    pub common: Common,
+    pub tie: bool,
+    pub order: BitOrder,
+    pub survivors: usize,
+}
+
+impl Criteria {
+    pub const OXYGEN: Criteria = Criteria::new(Common::Most);
+    pub const CO2: Criteria = Criteria::new(Common::Least);
+
+    pub const fn new(common: Common) -> Self {
+        Criteria {
+            common,
+            tie: matches!(common, Common::Most),
+            order: BitOrder::MsbFirst,
+            survivors: 1,
+        }
+    }
+
+    /// The bit value to keep given how many candidates have each.
+    fn keep(&self, zeros: usize, ones: usize) -> bool {
+        match (zeros, ones) {
+            (0, _) => true,
+            (_, 0) => false,
+            _ if zeros == ones => self.tie,
+            _ => match self.common {
+                Common::Most => ones > zeros,
+                Common::Least => ones < zeros,
+            },
+        }
+    }
+}
+
+impl FromStr for Criteria {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut common = Common::Most;
+        let mut tie = None;
+        let mut order = BitOrder::MsbFirst;
+        let mut survivors = 1;
+
+        for term in s.split(',').map(str::trim) {
+            match term {
+                "most" => common = Common::Most,
+                "least" => common = Common::Least,
+                "tie=0" => tie = Some(false),
+                "tie=1" => tie = Some(true),
+                "msb" => order = BitOrder::MsbFirst,
+                "lsb" => order = BitOrder::LsbFirst,
+                _ => match term.strip_prefix("keep=").map(str::parse) {
+                    Some(Ok(keep)) if keep > 0 => survivors = keep,
+                    _ => {
+                        return Err(format!(
+                            "unknown criteria term '{}', expected most, least, tie=0, tie=1, \
+                             msb, lsb or keep=K with K at least 1",
+                            term
+                        ))
+                    }
+                },
+            }
+        }
+
+        Ok(Criteria {
+            tie: tie.unwrap_or(common == Common::Most),
+            order,
+            survivors,
+            ..Criteria::new(common)
+        })
+    }
+}
+
+impl fmt::Display for Criteria {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{},tie={},{},keep={}",
+            match self.common {
+                Common::Most => "most",
+                Common::Least => "least",
+            },
+            self.tie as u8,
+            match self.order {
+                BitOrder::MsbFirst => "msb",
+                BitOrder::LsbFirst => "lsb",
+            },
+            self.survivors
+        )
+    }
+}
+
+/// Diagnostic numbers, all of the same width.
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub width: usize,
+    pub rows: Vec<Bits>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Node {
+    count: usize,
+    // Index of the child for a 0 and a 1 bit; the root is never a child, so
+    // 0 means there is none.
+    children: [usize; 2],
+}
+
+/// A binary trie over every diagnostic number, taking bits in the given
+/// order, where each node counts the numbers below it.
+#[derive(Debug, Clone)]
+pub struct Trie {
+    width: usize,
+    order: BitOrder,
+    nodes: Vec<Node>,
+}
+
+impl Trie {
+    pub fn new(report: &Report, order: BitOrder) -> Self {
+        let mut nodes = vec![Node::default()];
+
+        for row in &report.rows {
+            let mut node = 0;
+            nodes[node].count += 1;
+
+            for step in 0..report.width {
+                let bit = row.get(order.position(step, report.width)) as usize;
+
+                if nodes[node].children[bit] == 0 {
+                    nodes.push(Node::default());
+                    nodes[node].children[bit] = nodes.len() - 1;
+                }
+
+                node = nodes[node].children[bit];
+                nodes[node].count += 1;
+            }
+        }
+
+        Trie {
+            width: report.width,
+            order,
+            nodes,
+        }
+    }
+
+    fn count(&self, node: usize) -> usize {
+        if node == 0 {
+            0
+        } else {
+            self.nodes[node].count
+        }
+    }
+
+    /// Pushes every number below `node` onto `survivors`, once per copy.
+    fn collect(&self, node: usize, step: usize, value: &mut Bits, survivors: &mut Vec<Bits>) {
+        if step == self.width {
+            survivors.extend(std::iter::repeat_n(value.clone(), self.count(node)));
+            return;
+        }
+
+        for (bit, &child) in self.nodes[node].children.iter().enumerate() {
+            if child != 0 {
+                value.set(self.order.position(step, self.width), bit == 1);
+                self.collect(child, step + 1, value, survivors);
+            }
+        }
+    }
+
+    /// Walks down from the root until at most `criteria.survivors` numbers
+    /// are left below the current node, and returns them.
+    pub fn survivors(&self, criteria: &Criteria) -> Result<Vec<Bits>> {
+        assert_eq!(
+            self.order, criteria.order,
+            "trie built for another bit order"
+        );
+
+        if self.nodes[0].count == 0 {
+            return Err(Error::msg("the diagnostic report is empty"));
+        }
+
+        let mut value = Bits::zeros(self.width);
+        let mut node = 0;
+        let mut step = 0;
+
+        while self.nodes[node].count > criteria.survivors {
+            if step == self.width {
+                return Err(Error::msg(format!(
+                    "diagnostic number {} appears {} times, so the filter cannot stop at {}",
+                    value, self.nodes[node].count, criteria.survivors
+                )));
+            }
+
+            let [zeros, ones] = self.nodes[node].children;
+            let bit = criteria.keep(self.count(zeros), self.count(ones));
+
+            value.set(self.order.position(step, self.width), bit);
+            node = self.nodes[node].children[bit as usize];
+            step += 1;
+        }
+
+        let mut survivors = Vec::new();
+        self.collect(node, step, &mut value, &mut survivors);
+        Ok(survivors)
+    }
+
+    /// The single number the criteria select.
+    pub fn find_value(&self, criteria: &Criteria) -> Result<Bits> {
+        let criteria = Criteria {
+            survivors: 1,
+            ..*criteria
+        };
+
+        Ok(self.survivors(&criteria)?.remove(0))
+    }
+}
+
+pub fn parse_report(input: &str) -> Result<Report, ParseError> {
+    let mut width = None;
+
+    let rows = input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let line = line.trim_end();
+            let bits = Bits::from_binary(line)
+                .map_err(|pos| ParseError::at(input, &line[pos..], "'0' or '1'"))?;
+
+            match *width.get_or_insert(line.len()) {
+                width if line.len() < width => Err(ParseError::at(
+                    input,
+                    &line[line.len()..],
+                    format!("{} bits per diagnostic number", width),
+                )),
+                width if line.len() > width => Err(ParseError::at(
+                    input,
+                    &line[width..],
+                    format!("end of line after {} bits", width),
+                )),
+                _ => Ok(bits),
+            }
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Report {
+        width: width.unwrap_or(0),
+        rows,
+    })
+}
+
+/// How many diagnostic numbers have a 1 at each position.
+pub fn bit_sums(report: &Report) -> Vec<usize> {
+    let mut bit_sums = vec![0; report.width];
+
+    for row in &report.rows {
+        for pos in row.ones() {
+            bit_sums[pos] += 1;
+        }
+    }
+
+    bit_sums
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BitStat {
+    pub position: usize,
+    pub ones: usize,
+    pub zeros: usize,
+    /// Shannon entropy of the column in bits, 1 for a perfect tie.
+    pub entropy: f64,
+    pub tie: bool,
+}
+
+/// Per-position statistics over a diagnostic report.
+#[derive(Debug, Clone, Serialize)]
+pub struct BitStats {
+    pub rows: usize,
+    pub bits: Vec<BitStat>,
+    /// Positions with as many ones as zeros.
+    pub ties: Vec<usize>,
+    /// Pearson correlation between every pair of positions, `None` where a
+    /// position never changes.
+    pub correlation: Vec<Vec<Option<f64>>>,
+}
+
+fn entropy(ones: usize, rows: usize) -> f64 {
+    [ones, rows - ones]
+        .into_iter()
+        .filter(|&count| count > 0)
+        .map(|count| {
+            let p = count as f64 / rows as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+impl BitStats {
+    pub fn new(report: &Report) -> Self {
+        let rows = report.rows.len();
+        let sums = bit_sums(report);
+
+        // How many numbers have a 1 at both positions.
+        let mut both = vec![vec![0usize; report.width]; report.width];
+        for row in &report.rows {
+            let ones: Vec<usize> = row.ones().collect();
+            for &i in &ones {
+                for &j in &ones {
+                    both[i][j] += 1;
+                }
+            }
+        }
+
+        let correlation = (0..report.width)
+            .map(|i| {
+                (0..report.width)
+                    .map(|j| {
+                        let (n, a, b) = (rows as f64, sums[i] as f64, sums[j] as f64);
+                        let spread = (a * (n - a) * b * (n - b)).sqrt();
+                        (spread > 0.0).then(|| (n * both[i][j] as f64 - a * b) / spread)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let bits: Vec<BitStat> = sums
+            .into_iter()
+            .enumerate()
+            .map(|(position, ones)| BitStat {
+                position,
+                ones,
+                zeros: rows - ones,
+                entropy: entropy(ones, rows),
+                tie: ones * 2 == rows,
+            })
+            .collect();
+
+        BitStats {
+            rows,
+            ties: bits
+                .iter()
+                .filter(|bit| bit.tie)
+                .map(|bit| bit.position)
+                .collect(),
+            bits,
+            correlation,
+        }
+    }
+}
+
+pub fn part1(report: &Report) -> BigUint {
+    let line_count = report.rows.len();
+    let mut gamma = Bits::zeros(report.width);
+
+    for (pos, bit_sum) in bit_sums(report).into_iter().enumerate() {
+        gamma.set(pos, bit_sum * 2 >= line_count);
+    }
+
+    let epsilon = gamma.complement();
+
+    gamma.to_biguint() * epsilon.to_biguint()
+}
+
+pub fn part2(report: &Report) -> Result<BigUint> {
+    let trie = Trie::new(report, BitOrder::MsbFirst);
+    let oxygen = trie
+        .find_value(&Criteria::OXYGEN)
+        .context("no unique oxygen generator rating")?;
+    let co2 = trie
+        .find_value(&Criteria::CO2)
+        .context("no unique CO2 scrubber rating")?;
+
+    Ok(oxygen.to_biguint() * co2.to_biguint())
+}
+
+pub struct BinaryDiagnostic;
+
+impl Solver for BinaryDiagnostic {
+    type Input = Report;
+
+    const DAY: u8 = 3;
+    const NAME: &'static str = "Binary Diagnostic";
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Ok(parse_report(input)?)
+    }
+
+    fn part1(&self, report: &Self::Input) -> Result<Answer> {
+        Ok(part1(report).into())
+    }
+
+    fn part2(&self, report: &Self::Input) -> Result<Answer> {
+        Ok(part2(report)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::assert_example;
+
+    #[test]
+    fn test_example() {
+        assert_example(
+            BinaryDiagnostic,
+            include_str!("../fixtures/day3.txt"),
+            Answer::Number(198),
+            Answer::Number(230),
+        );
+    }
+
+    #[test]
+    fn test_wide_report() {
+        let report = parse_report(&format!(
+            "1{zeros}0\n1{zeros}1\n0{zeros}1\n",
+            zeros = "0".repeat(100)
+        ))
+        .unwrap();
+
+        assert_eq!(report.width, 102);
+        let oxygen: BigUint = (BigUint::from(1u8) << 101) + 1u8;
+        let epsilon: BigUint = (BigUint::from(1u8) << 101) - 2u8;
+
+        assert_eq!(part1(&report), oxygen.clone() * epsilon);
+        assert_eq!(part2(&report).unwrap(), oxygen);
+    }
+
+    #[test]
+    fn test_duplicate_rating() {
+        let report = parse_report("110\n110\n001\n").unwrap();
+
+        assert_eq!(
+            format!("{:#}", part2(&report).unwrap_err()),
+            "no unique oxygen generator rating: \
+             diagnostic number 110 appears 2 times, so the filter cannot stop at 1"
+        );
+    }
+
+    #[test]
+    fn test_criteria() {
+        let report = parse_report(include_str!("../fixtures/day3.txt")).unwrap();
+        let value = |criteria: &str| {
+            let criteria: Criteria = criteria.parse().unwrap();
+            Trie::new(&report, criteria.order)
+                .survivors(&criteria)
+                .unwrap()
+                .iter()
+                .map(Bits::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(value("most"), ["10111"]);
+        assert_eq!(value("least"), ["01010"]);
+        assert_eq!(value("most,keep=3"), ["10101", "10110", "10111"]);
+        assert_eq!(value("least,lsb"), ["11001"]);
+        assert_eq!(
+            "most,tie=1,lsb".parse::<Criteria>().unwrap().to_string(),
+            "most,tie=1,lsb,keep=1"
+        );
+        assert!("keep=0".parse::<Criteria>().is_err());
+    }
+
+    #[test]
+    fn test_bit_stats() {
+        let report = parse_report("10\n11\n01\n00\n").unwrap();
+        let stats = BitStats::new(&report);
+
+        assert_eq!(stats.ties, [0, 1]);
+        assert_eq!(stats.bits[0].entropy, 1.0);
+        assert_eq!(stats.correlation[0][0], Some(1.0));
+        assert_eq!(stats.correlation[0][1], Some(0.0));
+
+        let report = parse_report("10\n11\n").unwrap();
+        let stats = BitStats::new(&report);
+
+        assert_eq!(stats.bits[0].entropy, 0.0);
+        assert_eq!(stats.correlation[0][1], None);
+    }
+}
@@ -0,0 +1,282 @@
+use crate::parse::{self, ParseError, Parsed};
+use crate::solver::{Answer, Solver};
+use anyhow::{Error, Result};
+use nom::{
+    character::complete as character,
+    combinator,
+    error::{VerboseError, VerboseErrorKind},
+    multi, sequence, Err,
+};
+use std::fmt;
+
+pub mod game;
+pub mod generate;
+pub mod odds;
+pub mod optimize;
+pub mod rules;
+
+pub use game::{Game, Timeline, Win};
+pub use rules::{Mask, Pattern, WinRule};
+
+/// A bingo card of any rectangular size, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Board {
+    width: usize,
+    height: usize,
+    cells: Vec<usize>,
+}
+
+impl Board {
+    /// Builds a board from its rows, or `None` if it is empty or ragged.
+    pub fn new(rows: Vec<Vec<usize>>) -> Option<Self> {
+        let width = rows.first()?.len();
+        if width == 0 || rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The numbers on the board, row by row.
+    pub fn numbers(&self) -> impl Iterator<Item = usize> + '_ {
+        self.cells.iter().copied()
+    }
+
+    fn parse(input: &str) -> Parsed<'_, Self> {
+        parse::context("a bingo board", Board::parse_rows)(input)
+    }
+
+    /// Parses rows until a blank line, checking each has as many numbers as
+    /// the first.
+    fn parse_rows(input: &str) -> Parsed<'_, Self> {
+        let mut parse_row = sequence::terminated(
+            multi::many1(sequence::preceded(
+                character::space0,
+                combinator::consumed(parse::number),
+            )),
+            combinator::cut(parse::context("a number or end of row", parse::end_of_line)),
+        );
+
+        let (mut rest, first) = parse_row(input)?;
+        let width = first.len();
+        let mut rows = vec![first];
+
+        loop {
+            let (next, row) = match sequence::preceded(character::line_ending, &mut parse_row)(rest)
+            {
+                Ok(parsed) => parsed,
+                Err(Err::Error(_)) => break,
+                Err(e) => return Err(e),
+            };
+
+            if row.len() != width {
+                let at = match row.get(width) {
+                    Some((extra, _)) => *extra,
+                    None => {
+                        let (last, _) = row[row.len() - 1];
+                        &last[last.len()..]
+                    }
+                };
+
+                return Err(Err::Failure(VerboseError {
+                    errors: vec![(
+                        at,
+                        VerboseErrorKind::Context("as many numbers as the first row"),
+                    )],
+                }));
+            }
+
+            rows.push(row);
+            rest = next;
+        }
+
+        let rows = rows
+            .into_iter()
+            .map(|row| row.into_iter().map(|(_, number)| number).collect())
+            .collect();
+
+        Ok((
+            rest,
+            Board::new(rows).expect("rows were checked while parsing"),
+        ))
+    }
+}
+
+/// Writes the board in the puzzle input format.
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cell_width = self.numbers().max().map_or(1, |n| n.to_string().len());
+
+        for row in self.cells.chunks(self.width) {
+            let row: Vec<String> = row
+                .iter()
+                .map(|number| format!("{:>1$}", number, cell_width))
+                .collect();
+            writeln!(f, "{}", row.join(" "))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bingo {
+    pub rounds: Vec<usize>,
+    pub boards: Vec<Board>,
+}
+
+/// Writes the game in the format `parse_file` reads.
+impl fmt::Display for Bingo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rounds: Vec<String> = self.rounds.iter().map(usize::to_string).collect();
+        writeln!(f, "{}", rounds.join(","))?;
+
+        for board in &self.boards {
+            write!(f, "\n{}", board)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn parse_rounds(input: &str) -> Parsed<'_, Vec<usize>> {
+    sequence::terminated(
+        multi::separated_list1(
+            character::char(','),
+            parse::context("a drawn number", parse::number),
+        ),
+        parse::context("',' or end of line", parse::end_of_line),
+    )(input)
+}
+
+pub fn parse_file(input: &str) -> Parsed<'_, (Vec<usize>, Vec<Board>)> {
+    combinator::map(
+        sequence::tuple((
+            parse_rounds,
+            character::line_ending,
+            parse::context("blank line after the draws", character::line_ending),
+            multi::separated_list1(
+                sequence::tuple((character::line_ending, character::line_ending)),
+                Board::parse,
+            ),
+            character::multispace0,
+            parse::context("blank line between boards", combinator::eof),
+        )),
+        |(rounds, _, _, boards, _, _)| (rounds, boards),
+    )(input)
+}
+
+pub fn parse_bingo(input: &str) -> Result<Bingo, ParseError> {
+    let (rounds, boards) = parse::finish(input, parse_file(input))?;
+
+    Ok(Bingo { rounds, boards })
+}
+
+/// Score of the first board to win under `rules`.
+pub fn first_winner(bingo: &Bingo, rules: &[WinRule]) -> Result<Option<usize>> {
+    Ok(Timeline::new(bingo, rules)?.first().map(|win| win.score))
+}
+
+/// Score of the last board to win under `rules`, ignoring boards that
+/// never win.
+pub fn last_winner(bingo: &Bingo, rules: &[WinRule]) -> Result<Option<usize>> {
+    Ok(Timeline::new(bingo, rules)?.last().map(|win| win.score))
+}
+
+pub fn part1(bingo: &Bingo) -> Result<Option<usize>> {
+    first_winner(bingo, &[WinRule::Lines])
+}
+
+pub fn part2(bingo: &Bingo) -> Result<Option<usize>> {
+    last_winner(bingo, &[WinRule::Lines])
+}
+
+pub struct GiantSquid;
+
+impl Solver for GiantSquid {
+    type Input = Bingo;
+
+    const DAY: u8 = 4;
+    const NAME: &'static str = "Giant Squid";
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Ok(parse_bingo(input)?)
+    }
+
+    fn part1(&self, bingo: &Self::Input) -> Result<Answer> {
+        let score = part1(bingo)?.ok_or(Error::msg("no board ever wins"))?;
+
+        Ok(score.into())
+    }
+
+    fn part2(&self, bingo: &Self::Input) -> Result<Answer> {
+        let score = part2(bingo)?.ok_or(Error::msg("no board ever wins"))?;
+
+        Ok(score.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::assert_example;
+
+    #[test]
+    fn test_example() {
+        assert_example(
+            GiantSquid,
+            include_str!("../fixtures/day4.txt"),
+            Answer::Number(4512),
+            Answer::Number(1924),
+        );
+    }
+
+    #[test]
+    fn test_board_sizes() {
+        let bingo = parse_bingo("1,2,3,4,5,6\n\n1 2 3\n4 5 6\n\n1 2\n3 4\n5 6\n").unwrap();
+
+        assert_eq!(bingo.boards[0].to_string(), "1 2 3\n4 5 6\n");
+        assert_eq!(bingo.boards[0].width(), 3);
+        assert_eq!(bingo.boards[0].height(), 2);
+        assert_eq!(bingo.boards[1].width(), 2);
+        assert_eq!(bingo.boards[1].height(), 3);
+        assert_eq!(part1(&bingo).unwrap(), Some(2 * 18));
+        assert_eq!(part2(&bingo).unwrap(), Some(3 * 15));
+
+        assert_eq!(
+            parse_bingo("1,2\n\n1 2 3\n4 5\n").unwrap_err().to_string(),
+            "line 4, col 4: expected as many numbers as the first row in a bingo board"
+        );
+        assert_eq!(
+            parse_bingo("1,2\n\n1 2\n3 4 5\n").unwrap_err().to_string(),
+            "line 4, col 5: expected as many numbers as the first row in a bingo board"
+        );
+    }
+
+    #[test]
+    fn test_win_rules() {
+        let bingo = parse_bingo("1,5,9,3,7\n\n1 2 3\n4 5 6\n7 8 9\n").unwrap();
+        let score = |rule| first_winner(&bingo, &[rule]).unwrap();
+
+        assert_eq!(score(WinRule::Lines), None);
+        assert_eq!(score(WinRule::Diagonals), Some(9 * 30));
+        assert_eq!(score(WinRule::Corners), Some(7 * 20));
+        assert_eq!(score(WinRule::Blackout), None);
+        assert_eq!(
+            score(WinRule::Mask(Mask::parse("#\n.\n#\n").unwrap())),
+            Some(3 * 27)
+        );
+    }
+}
@@ -0,0 +1,324 @@
+//! Bingo engine that marks a draw in time proportional to how often the
+//! number appears on the boards.
+//!
+//! Every drawn number is looked up in an index of the cells holding it, and
+//! each winning pattern keeps a count of its cells still unmarked, so no
+//! board is ever rescanned.
+
+use super::rules::{self, Pattern, WinRule};
+use super::Bingo;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// A board completing one of its winning patterns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Win {
+    /// Index of the board in the input.
+    pub board: usize,
+    /// How many numbers had been drawn, starting at 1.
+    pub round: usize,
+    pub number: usize,
+    pub score: usize,
+}
+
+/// Winning patterns of one board size, with the patterns each cell is in.
+struct Layout {
+    patterns: Vec<Pattern>,
+    by_cell: Vec<Vec<usize>>,
+}
+
+impl Layout {
+    fn new(rules: &[WinRule], width: usize, height: usize) -> Result<Self> {
+        let patterns = rules::compile(rules, width, height)?;
+        let mut by_cell = vec![Vec::new(); width * height];
+
+        for (i, pattern) in patterns.iter().enumerate() {
+            for &cell in pattern {
+                by_cell[cell].push(i);
+            }
+        }
+
+        Ok(Layout { patterns, by_cell })
+    }
+}
+
+pub struct Game<'a> {
+    bingo: &'a Bingo,
+    layouts: Vec<Layout>,
+    layout_of: Vec<usize>,
+    /// Every (board, cell) holding each number.
+    index: HashMap<usize, Vec<(usize, usize)>>,
+    marked: Vec<Vec<bool>>,
+    /// Unmarked cells left in each pattern of each board.
+    remaining: Vec<Vec<usize>>,
+    unmarked_sum: Vec<usize>,
+    /// The pattern each board won with.
+    won: Vec<Option<usize>>,
+    round: usize,
+}
+
+impl<'a> Game<'a> {
+    pub fn new(bingo: &'a Bingo, rules: &[WinRule]) -> Result<Self> {
+        let mut layouts = Vec::new();
+        let mut sizes = HashMap::new();
+        let mut layout_of = Vec::with_capacity(bingo.boards.len());
+        let mut index: HashMap<usize, Vec<(usize, usize)>> = HashMap::new();
+
+        for (i, board) in bingo.boards.iter().enumerate() {
+            let size = (board.width(), board.height());
+            let layout = match sizes.get(&size) {
+                Some(&layout) => layout,
+                None => {
+                    layouts.push(
+                        Layout::new(rules, size.0, size.1)
+                            .with_context(|| format!("board {}", i + 1))?,
+                    );
+                    sizes.insert(size, layouts.len() - 1);
+                    layouts.len() - 1
+                }
+            };
+            layout_of.push(layout);
+
+            for (cell, number) in board.numbers().enumerate() {
+                index.entry(number).or_default().push((i, cell));
+            }
+        }
+
+        Ok(Game {
+            marked: bingo
+                .boards
+                .iter()
+                .map(|board| vec![false; board.width() * board.height()])
+                .collect(),
+            remaining: layout_of
+                .iter()
+                .map(|&layout| layouts[layout].patterns.iter().map(Vec::len).collect())
+                .collect(),
+            unmarked_sum: bingo
+                .boards
+                .iter()
+                .map(|board| board.numbers().sum())
+                .collect(),
+            won: vec![None; bingo.boards.len()],
+            round: 0,
+            bingo,
+            layouts,
+            layout_of,
+            index,
+        })
+    }
+
+    /// Clears every mark, keeping the index, to play again from round 0.
+    pub fn reset(&mut self) {
+        for (i, board) in self.bingo.boards.iter().enumerate() {
+            self.marked[i].fill(false);
+            self.unmarked_sum[i] = board.numbers().sum();
+
+            let patterns = &self.layouts[self.layout_of[i]].patterns;
+            for (remaining, pattern) in self.remaining[i].iter_mut().zip(patterns) {
+                *remaining = pattern.len();
+            }
+        }
+
+        self.won.fill(None);
+        self.round = 0;
+    }
+
+    pub fn round(&self) -> usize {
+        self.round
+    }
+
+    pub fn is_marked(&self, board: usize, cell: usize) -> bool {
+        self.marked[board][cell]
+    }
+
+    pub fn has_won(&self, board: usize) -> bool {
+        self.won[board].is_some()
+    }
+
+    /// Cells of the pattern `board` won with.
+    pub fn winning_cells(&self, board: usize) -> Option<&[usize]> {
+        let pattern = self.won[board]?;
+        Some(&self.layouts[self.layout_of[board]].patterns[pattern])
+    }
+
+    /// Marks `number` everywhere it appears and returns the boards that win
+    /// with it, in input order.
+    pub fn draw(&mut self, number: usize) -> Vec<Win> {
+        self.round += 1;
+        let mut winners = Vec::new();
+
+        for &(board, cell) in self.index.get(&number).map_or(&[][..], Vec::as_slice) {
+            if self.marked[board][cell] {
+                continue;
+            }
+
+            self.marked[board][cell] = true;
+            self.unmarked_sum[board] -= number;
+
+            for &pattern in &self.layouts[self.layout_of[board]].by_cell[cell] {
+                self.remaining[board][pattern] -= 1;
+
+                if self.remaining[board][pattern] == 0 && self.won[board].is_none() {
+                    self.won[board] = Some(pattern);
+                    winners.push(board);
+                }
+            }
+        }
+
+        winners.sort_unstable();
+        winners
+            .into_iter()
+            .map(|board| Win {
+                board,
+                round: self.round,
+                number,
+                score: self.unmarked_sum[board] * number,
+            })
+            .collect()
+    }
+
+    /// Draws every remaining number, yielding each board's win in order.
+    ///
+    /// Only meaningful on a fresh or `reset` game, or one whose draws so far
+    /// were the first `round()` numbers of the input.
+    pub fn play(mut self) -> impl Iterator<Item = Win> + 'a {
+        let rounds = self.bingo.rounds.get(self.round..).unwrap_or(&[]);
+
+        rounds.iter().flat_map(move |&number| self.draw(number))
+    }
+}
+
+/// When every board wins, from a single pass over the draws.
+#[derive(Debug, Clone)]
+pub struct Timeline {
+    wins: Vec<Option<Win>>,
+    /// Boards in the order they win.
+    ranking: Vec<usize>,
+}
+
+impl Timeline {
+    pub fn new(bingo: &Bingo, rules: &[WinRule]) -> Result<Self> {
+        let mut wins = vec![None; bingo.boards.len()];
+        let mut ranking = Vec::new();
+
+        for win in Game::new(bingo, rules)?.play() {
+            wins[win.board] = Some(win);
+            ranking.push(win.board);
+        }
+
+        Ok(Timeline { wins, ranking })
+    }
+
+    /// The win of `board`, or `None` if it never wins.
+    pub fn board(&self, board: usize) -> Option<&Win> {
+        self.wins[board].as_ref()
+    }
+
+    /// The `rank`-th board to win, starting at 1.
+    pub fn kth(&self, rank: usize) -> Option<&Win> {
+        let board = *self.ranking.get(rank.checked_sub(1)?)?;
+        self.board(board)
+    }
+
+    pub fn first(&self) -> Option<&Win> {
+        self.kth(1)
+    }
+
+    pub fn last(&self) -> Option<&Win> {
+        self.kth(self.ranking.len())
+    }
+
+    /// Every win, in the order they happen.
+    pub fn ranked(&self) -> impl Iterator<Item = &Win> {
+        self.ranking.iter().filter_map(|&board| self.board(board))
+    }
+
+    /// Boards that never win.
+    pub fn never(&self) -> impl Iterator<Item = usize> + '_ {
+        self.wins
+            .iter()
+            .enumerate()
+            .filter(|(_, win)| win.is_none())
+            .map(|(board, _)| board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day4::parse_bingo;
+
+    #[test]
+    fn test_draws() {
+        let bingo = parse_bingo("4,4,1,2,3\n\n1 4\n4 2\n\n3 1\n2 9\n").unwrap();
+        let mut game = Game::new(&bingo, &[WinRule::Lines]).unwrap();
+
+        assert_eq!(game.draw(4), []);
+        assert!(game.is_marked(0, 1) && game.is_marked(0, 2));
+        assert_eq!(game.winning_cells(0), None);
+        assert_eq!(game.draw(4), []);
+        assert_eq!(
+            game.draw(1),
+            [Win {
+                board: 0,
+                round: 3,
+                number: 1,
+                score: 2
+            }]
+        );
+        assert_eq!(game.winning_cells(0), Some(&[0, 1][..]));
+        let mut replayed = Game::new(&bingo, &[WinRule::Lines]).unwrap();
+        replayed.draw(2);
+        replayed.reset();
+        for number in [4, 4, 1] {
+            replayed.draw(number);
+        }
+        assert_eq!(replayed.winning_cells(0), game.winning_cells(0));
+        assert!(!replayed.is_marked(0, 3));
+
+        assert_eq!(
+            game.play().collect::<Vec<_>>(),
+            [Win {
+                board: 1,
+                round: 5,
+                number: 3,
+                score: 3 * 9
+            }]
+        );
+
+        let mut overdrawn = Game::new(&bingo, &[WinRule::Lines]).unwrap();
+        for number in [7; 6] {
+            overdrawn.draw(number);
+        }
+        assert_eq!(overdrawn.play().count(), 0);
+    }
+
+    #[test]
+    fn test_timeline() {
+        let bingo = parse_bingo(include_str!("../../fixtures/day4.txt")).unwrap();
+        let timeline = Timeline::new(&bingo, &[WinRule::Lines]).unwrap();
+
+        assert_eq!(
+            timeline.first().map(|win| (win.board, win.score)),
+            Some((2, 4512))
+        );
+        assert_eq!(timeline.kth(2).map(|win| win.board), Some(0));
+        assert_eq!(
+            timeline.last().map(|win| (win.board, win.score)),
+            Some((1, 1924))
+        );
+        assert_eq!(timeline.kth(0), None);
+        assert_eq!(timeline.kth(4), None);
+        assert_eq!(timeline.never().count(), 0);
+
+        let bingo = Bingo {
+            rounds: bingo.rounds[..11].to_vec(),
+            ..bingo
+        };
+        let timeline = Timeline::new(&bingo, &[WinRule::Lines]).unwrap();
+        assert_eq!(timeline.never().collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!(timeline.first(), None);
+    }
+}
@@ -0,0 +1,172 @@
+//! Seeded random bingo inputs.
+
+use super::game::Timeline;
+use super::rules::WinRule;
+use super::{Bingo, Board};
+use anyhow::{Error, Result};
+use rand::seq::index;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// How many games to deal before giving up on the constraints.
+pub const MAX_ATTEMPTS: usize = 1000;
+
+#[derive(Debug, Clone)]
+pub struct Spec {
+    pub boards: usize,
+    pub width: usize,
+    pub height: usize,
+    /// Numbers are drawn from `0..numbers`, each at most once per board.
+    pub numbers: usize,
+    /// How many numbers are drawn, all of them when `None`.
+    pub draws: Option<usize>,
+    /// Require every board to win, with a single board winning in the first
+    /// winning round and a single board in the last one.
+    pub unique_winners: bool,
+    pub rules: Vec<WinRule>,
+}
+
+impl Default for Spec {
+    /// The shape of the real puzzle input.
+    fn default() -> Self {
+        Spec {
+            boards: 100,
+            width: 5,
+            height: 5,
+            numbers: 100,
+            draws: None,
+            unique_winners: false,
+            rules: vec![WinRule::Lines],
+        }
+    }
+}
+
+fn deal(spec: &Spec, rng: &mut ChaCha8Rng) -> Bingo {
+    let draws = spec.draws.unwrap_or(spec.numbers);
+    let rounds = index::sample(rng, spec.numbers, draws).into_vec();
+
+    let boards = (0..spec.boards)
+        .map(|_| {
+            let numbers = index::sample(rng, spec.numbers, spec.width * spec.height).into_vec();
+            let rows = numbers.chunks(spec.width).map(<[usize]>::to_vec).collect();
+            Board::new(rows).expect("rows have the board width")
+        })
+        .collect();
+
+    Bingo { rounds, boards }
+}
+
+fn has_unique_winners(bingo: &Bingo, timeline: &Timeline) -> bool {
+    let (first, last) = match (timeline.first(), timeline.last()) {
+        (Some(first), Some(last)) => (first.round, last.round),
+        _ => return false,
+    };
+    let winners_at = |round| timeline.ranked().filter(|win| win.round == round).count();
+
+    timeline.ranked().count() == bingo.boards.len()
+        && winners_at(first) == 1
+        && winners_at(last) == 1
+}
+
+/// Deals random games from `seed` until one meets the spec.
+pub fn generate(spec: &Spec, seed: u64) -> Result<Bingo> {
+    if spec.boards == 0 || spec.width == 0 || spec.height == 0 {
+        return Err(Error::msg("need at least one board of at least 1x1"));
+    }
+    let cells = spec.width.checked_mul(spec.height).ok_or_else(|| {
+        Error::msg(format!(
+            "a {}x{} board has too many cells",
+            spec.width, spec.height
+        ))
+    })?;
+    if cells > spec.numbers {
+        return Err(Error::msg(format!(
+            "a {}x{} board needs at least {} distinct numbers, not {}",
+            spec.width, spec.height, cells, spec.numbers
+        )));
+    }
+    if spec
+        .draws
+        .is_some_and(|draws| draws == 0 || draws > spec.numbers)
+    {
+        return Err(Error::msg(format!(
+            "can draw between 1 and {} numbers",
+            spec.numbers
+        )));
+    }
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+    for _ in 0..MAX_ATTEMPTS {
+        let bingo = deal(spec, &mut rng);
+
+        if !spec.unique_winners {
+            return Ok(bingo);
+        }
+
+        if has_unique_winners(&bingo, &Timeline::new(&bingo, &spec.rules)?) {
+            return Ok(bingo);
+        }
+    }
+
+    Err(Error::msg(format!(
+        "no game with unique first and last winners in {} attempts",
+        MAX_ATTEMPTS
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day4::parse_bingo;
+
+    #[test]
+    fn test_generate() {
+        let spec = Spec {
+            boards: 20,
+            width: 4,
+            height: 3,
+            numbers: 40,
+            unique_winners: true,
+            ..Spec::default()
+        };
+        let bingo = generate(&spec, 7).unwrap();
+        let parsed = parse_bingo(&bingo.to_string()).unwrap();
+
+        assert_eq!(parsed, bingo);
+        assert_eq!(generate(&spec, 7).unwrap(), bingo);
+        assert_eq!(bingo.rounds.len(), 40);
+        assert!(bingo.boards.iter().all(|board| board.width() == 4));
+        assert!(has_unique_winners(
+            &bingo,
+            &Timeline::new(&bingo, &spec.rules).unwrap()
+        ));
+
+        // The same seed has to give the same puzzle on every build.
+        let spec = Spec {
+            boards: 1,
+            width: 2,
+            height: 2,
+            numbers: 10,
+            ..Spec::default()
+        };
+        assert_eq!(
+            generate(&spec, 7).unwrap().to_string(),
+            "4,8,3,1,9,2,7,6,5,0\n\n0 2\n3 4\n"
+        );
+
+        let spec = Spec {
+            boards: 1,
+            numbers: usize::MAX,
+            draws: Some(5),
+            ..Spec::default()
+        };
+        assert_eq!(generate(&spec, 7).unwrap().rounds.len(), 5);
+        let spec = Spec {
+            width: usize::MAX,
+            height: 2,
+            ..Spec::default()
+        };
+        assert!(generate(&spec, 7).is_err());
+    }
+}
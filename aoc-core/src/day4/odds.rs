@@ -0,0 +1,366 @@
+//! How likely each board is to win when the draws come in random order.
+//!
+//! A board "wins first" when no other board has won in an earlier round, so
+//! boards completing in the same round all count as first.
+//!
+//! `simulate` shuffles the draws for a number of trials across threads.
+//! `exact` instead walks every subset of the draws: after `t` draws each
+//! `t`-subset is equally likely, and the chance that the draws before round
+//! `t` are exactly `A` and the `t`-th draw is `x` is `(t-1)! (n-t)! / n!`.
+
+use super::game::Game;
+use super::rules::{self, WinRule};
+use super::Bingo;
+use anyhow::{Error, Result};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Largest number of draws `exact` enumerates the subsets of.
+pub const MAX_EXACT_DRAWS: usize = 20;
+
+/// Largest table `exact` allocates, in words of 64 boards per subset.
+pub const MAX_EXACT_WORDS: usize = 1 << 25;
+
+/// Batches the trials are split into, each with its own seed so the totals
+/// depend on the trial count alone and not on the thread count.
+const BATCHES: usize = 256;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BoardOdds {
+    pub board: usize,
+    /// Probability that no other board wins in an earlier round.
+    pub first: f64,
+    /// Probability of winning in each round, starting at round 1.
+    pub rounds: Vec<f64>,
+    /// Probability of not winning before the draws run out.
+    pub never: f64,
+}
+
+impl BoardOdds {
+    /// Expected winning round, given that the board wins at all.
+    pub fn mean_round(&self) -> Option<f64> {
+        let wins: f64 = self.rounds.iter().sum();
+
+        (wins > 0.0).then(|| {
+            let total: f64 = (1..)
+                .zip(&self.rounds)
+                .map(|(round, p)| round as f64 * p)
+                .sum();
+            total / wins
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Odds {
+    /// Number of shuffles behind the estimate, `None` when exact.
+    pub trials: Option<usize>,
+    pub boards: Vec<BoardOdds>,
+}
+
+#[derive(Debug, Clone)]
+struct Tally {
+    first: Vec<usize>,
+    rounds: Vec<Vec<usize>>,
+}
+
+impl Tally {
+    fn new(boards: usize, draws: usize) -> Self {
+        Tally {
+            first: vec![0; boards],
+            rounds: vec![vec![0; draws]; boards],
+        }
+    }
+
+    fn add(&mut self, other: &Tally) {
+        for (a, b) in self.first.iter_mut().zip(&other.first) {
+            *a += b;
+        }
+        for (a, b) in self.rounds.iter_mut().zip(&other.rounds) {
+            for (a, b) in a.iter_mut().zip(b) {
+                *a += b;
+            }
+        }
+    }
+}
+
+fn run_batch(game: &mut Game, draws: &mut [usize], trials: usize, seed: u64, tally: &mut Tally) {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let boards = tally.first.len();
+
+    for _ in 0..trials {
+        draws.shuffle(&mut rng);
+        game.reset();
+
+        let mut first_round = None;
+        let mut won = 0;
+
+        for &number in draws.iter() {
+            let wins = game.draw(number);
+
+            for win in &wins {
+                tally.rounds[win.board][win.round - 1] += 1;
+
+                if *first_round.get_or_insert(win.round) == win.round {
+                    tally.first[win.board] += 1;
+                }
+            }
+
+            won += wins.len();
+            if won == boards {
+                break;
+            }
+        }
+    }
+}
+
+/// Estimates the odds from `trials` shuffles of the draws, seeded by `seed`,
+/// on `threads` threads.
+pub fn simulate(
+    bingo: &Bingo,
+    rules: &[WinRule],
+    trials: usize,
+    seed: u64,
+    threads: usize,
+) -> Result<Odds> {
+    if trials == 0 {
+        return Err(Error::msg("need at least one trial to estimate the odds"));
+    }
+
+    let boards = bingo.boards.len();
+    let draws = bingo.rounds.len();
+    // Fail on bad rules before starting any thread.
+    Game::new(bingo, rules)?;
+
+    let batch_size = trials.div_ceil(BATCHES);
+    let mut master = ChaCha8Rng::seed_from_u64(seed);
+    let seeds: Vec<u64> = (0..trials.div_ceil(batch_size))
+        .map(|_| master.gen())
+        .collect();
+    let next = AtomicUsize::new(0);
+    let total = Mutex::new(Tally::new(boards, draws));
+
+    thread::scope(|scope| {
+        // Every thread keeps its own tally, so never start one without work.
+        for _ in 0..threads.clamp(1, seeds.len()) {
+            scope.spawn(|| {
+                let mut game = Game::new(bingo, rules).expect("rules were checked");
+                let mut draws = bingo.rounds.clone();
+                let mut tally = Tally::new(boards, draws.len());
+
+                loop {
+                    let batch = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(&seed) = seeds.get(batch) else {
+                        break;
+                    };
+                    let size = batch_size.min(trials - batch * batch_size);
+                    draws.copy_from_slice(&bingo.rounds);
+                    run_batch(&mut game, &mut draws, size, seed, &mut tally);
+                }
+
+                total.lock().unwrap().add(&tally);
+            });
+        }
+    });
+
+    let tally = total.into_inner().unwrap();
+    let share = |count: usize| count as f64 / trials as f64;
+
+    Ok(Odds {
+        trials: Some(trials),
+        boards: (0..boards)
+            .map(|board| {
+                let rounds: Vec<f64> = tally.rounds[board].iter().map(|&n| share(n)).collect();
+                BoardOdds {
+                    board,
+                    first: share(tally.first[board]),
+                    never: share(trials - tally.rounds[board].iter().sum::<usize>()),
+                    rounds,
+                }
+            })
+            .collect(),
+    })
+}
+
+/// Computes the odds exactly by enumerating every subset of the draws.
+pub fn exact(bingo: &Bingo, rules: &[WinRule]) -> Result<Odds> {
+    let n = bingo.rounds.len();
+    let boards = bingo.boards.len();
+    let words = boards.div_ceil(64);
+
+    if n > MAX_EXACT_DRAWS || (1 << n) * words > MAX_EXACT_WORDS {
+        return Err(Error::msg(format!(
+            "exact odds need at most {} draws and 2^draws * ceil(boards / 64) at most {}, \
+             not {} draws and {} boards",
+            MAX_EXACT_DRAWS, MAX_EXACT_WORDS, n, boards
+        )));
+    }
+    if bingo.rounds.iter().collect::<HashSet<_>>().len() != n {
+        return Err(Error::msg(
+            "exact odds need every drawn number to be distinct",
+        ));
+    }
+
+    // won[s * words..] holds the boards that have won once the draws in the
+    // bit set `s` are out: those with a pattern equal to `s`, plus those that
+    // have won on any smaller set.
+    let mut won = vec![0u64; (1 << n) * words];
+    for (i, board) in bingo.boards.iter().enumerate() {
+        let numbers: Vec<usize> = board.numbers().collect();
+
+        for pattern in rules::compile(rules, board.width(), board.height())? {
+            let mask = pattern.iter().try_fold(0usize, |mask, &cell| {
+                let draw = bingo.rounds.iter().position(|&n| n == numbers[cell])?;
+                Some(mask | 1 << draw)
+            });
+
+            if let Some(mask) = mask {
+                won[mask * words + i / 64] |= 1 << (i % 64);
+            }
+        }
+    }
+    for set in 1..1usize << n {
+        for draw in 0..n {
+            if set & 1 << draw != 0 {
+                let smaller = set & !(1 << draw);
+                for word in 0..words {
+                    won[set * words + word] |= won[smaller * words + word];
+                }
+            }
+        }
+    }
+
+    // binomial[k] = C(n - 1, k), for the chance of one draw sequence.
+    let mut binomial = vec![1.0f64; n.max(1)];
+    for k in 1..n {
+        binomial[k] = binomial[k - 1] * (n - k) as f64 / k as f64;
+    }
+
+    let mut first = vec![0.0; boards];
+    let mut rounds = vec![vec![0.0; n]; boards];
+
+    for set in 0..1usize << n {
+        let t = set.count_ones() as usize;
+        let won_here = &won[set * words..(set + 1) * words];
+        if won_here.iter().all(|&word| word == 0) {
+            // Nobody has won yet: whatever wins on the next draw wins first.
+            for draw in (0..n).filter(|draw| set & 1 << draw == 0) {
+                let next = set | 1 << draw;
+                let chance = 1.0 / (n as f64 * binomial[t]);
+
+                for (word, &bits) in won[next * words..(next + 1) * words].iter().enumerate() {
+                    let mut bits = bits;
+                    while bits != 0 {
+                        let board = word * 64 + bits.trailing_zeros() as usize;
+                        bits &= bits - 1;
+                        first[board] += chance;
+                    }
+                }
+            }
+        }
+
+        // Every t-subset is equally likely after t draws; tally how often
+        // each board has won by then, and difference the rounds below.
+        if t > 0 {
+            let chance = 1.0 / (binomial[t - 1] * n as f64 / t as f64);
+            for (word, &bits) in won_here.iter().enumerate() {
+                let mut bits = bits;
+                while bits != 0 {
+                    let board = word * 64 + bits.trailing_zeros() as usize;
+                    bits &= bits - 1;
+                    rounds[board][t - 1] += chance;
+                }
+            }
+        }
+    }
+
+    Ok(Odds {
+        trials: None,
+        boards: (0..boards)
+            .map(|board| {
+                // rounds[board] holds P(won by round t); turn it into P(wins at t).
+                let by_round = &rounds[board];
+                let at_round: Vec<f64> = (0..n)
+                    .map(|t| by_round[t] - if t > 0 { by_round[t - 1] } else { 0.0 })
+                    .collect();
+
+                BoardOdds {
+                    board,
+                    first: first[board],
+                    never: (1.0 - by_round.last().copied().unwrap_or(0.0)).max(0.0),
+                    rounds: at_round,
+                }
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day4::{parse_bingo, Board};
+
+    #[test]
+    fn test_exact() {
+        // The first board needs 1 and 2, the second 3 alone, in any order of
+        // four draws.
+        let bingo = parse_bingo("1,2,3,4\n\n1 2\n\n3\n").unwrap();
+        let odds = exact(&bingo, &[WinRule::Blackout]).unwrap();
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+        // The first board only wins first when 3 comes after both 1 and 2.
+        assert!(close(odds.boards[0].first, 1.0 / 3.0));
+        assert!(close(odds.boards[1].first, 2.0 / 3.0));
+        assert!(close(odds.boards[1].rounds[0], 0.25));
+        assert!(close(odds.boards[0].rounds[1], 1.0 / 6.0));
+        assert!(close(odds.boards[0].never, 0.0));
+        assert!(close(odds.boards[1].mean_round().unwrap(), 2.5));
+    }
+
+    #[test]
+    fn test_simulate() {
+        let bingo = parse_bingo("1,2,3,4\n\n1 2\n\n3\n").unwrap();
+        let exact = exact(&bingo, &[WinRule::Blackout]).unwrap();
+        let estimate = simulate(&bingo, &[WinRule::Blackout], 20_000, 1, 4).unwrap();
+
+        for (exact, estimate) in exact.boards.iter().zip(&estimate.boards) {
+            assert!((exact.first - estimate.first).abs() < 0.02);
+            for (exact, estimate) in exact.rounds.iter().zip(&estimate.rounds) {
+                assert!((exact - estimate).abs() < 0.02);
+            }
+        }
+
+        let again = simulate(&bingo, &[WinRule::Blackout], 20_000, 1, 1).unwrap();
+        assert_eq!(again.boards[0].first, estimate.boards[0].first);
+
+        // Fewer trials than batches still spread over threads the same way.
+        let few = simulate(&bingo, &[WinRule::Blackout], 100, 1, 8).unwrap();
+        let again = simulate(&bingo, &[WinRule::Blackout], 100, 1, 1).unwrap();
+        assert_eq!(few.boards[0].rounds, again.boards[0].rounds);
+        // And the same seed gives the same estimate on every build.
+        assert_eq!(few.boards[0].first, 0.32);
+
+        assert!(simulate(&bingo, &[WinRule::Blackout], 0, 1, 1).is_err());
+    }
+
+    #[test]
+    fn test_exact_limits() {
+        let board = Board::new(vec![vec![0]]).unwrap();
+        let bingo = Bingo {
+            rounds: (0..20).collect(),
+            boards: vec![board; 64 * 33],
+        };
+
+        assert_eq!(
+            exact(&bingo, &[WinRule::Blackout]).unwrap_err().to_string(),
+            "exact odds need at most 20 draws and 2^draws * ceil(boards / 64) at most 33554432, \
+             not 20 draws and 2112 boards"
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Shortest draw sequences that make a chosen board win first.
+//!
+//! A board wins once every number of one of its patterns is drawn, so the
+//! shortest sequence for a target board is the number set of one of its
+//! patterns. Extra draws only help other boards, so it is enough to find the
+//! smallest pattern whose numbers complete no pattern of any other board.
+//! When every pattern of the target also completes another board, no draw
+//! order can make it win first.
+
+use super::rules::{self, Pattern, WinRule};
+use super::Bingo;
+use anyhow::{Error, Result};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+/// A draw sequence after which only the target board has won.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub board: usize,
+    /// Numbers to draw, in the order they appear in the original draws.
+    pub draws: Vec<usize>,
+    pub score: usize,
+}
+
+/// Finds the shortest subsequence of the draws after which `target` is the
+/// only winning board, or `None` if it can never win first.
+pub fn fastest_win(bingo: &Bingo, rules: &[WinRule], target: usize) -> Result<Option<Plan>> {
+    let board = bingo.boards.get(target).ok_or_else(|| {
+        Error::msg(format!(
+            "there is no board {}, only {}",
+            target + 1,
+            bingo.boards.len()
+        ))
+    })?;
+
+    let mut layouts: HashMap<(usize, usize), Vec<Pattern>> = HashMap::new();
+    for board in &bingo.boards {
+        let size = (board.width(), board.height());
+        if let Entry::Vacant(entry) = layouts.entry(size) {
+            entry.insert(rules::compile(rules, size.0, size.1)?);
+        }
+    }
+
+    // Where each number is first drawn; numbers never drawn are unavailable.
+    let mut drawn_at = HashMap::new();
+    for (i, &number) in bingo.rounds.iter().enumerate() {
+        drawn_at.entry(number).or_insert(i);
+    }
+
+    let numbers: Vec<usize> = board.numbers().collect();
+    let mut candidates: Vec<Vec<usize>> = layouts[&(board.width(), board.height())]
+        .iter()
+        .map(|pattern| {
+            let mut draws: Vec<usize> = pattern.iter().map(|&cell| numbers[cell]).collect();
+            draws.sort_unstable();
+            draws.dedup();
+            draws
+        })
+        .filter(|draws| draws.iter().all(|number| drawn_at.contains_key(number)))
+        .map(|mut draws| {
+            draws.sort_by_key(|number| drawn_at[number]);
+            draws
+        })
+        .collect();
+
+    // Fewest draws first, then the one finishing earliest in the real draws.
+    candidates.sort_by_key(|draws| (draws.len(), draws.last().map(|number| drawn_at[number])));
+
+    for draws in candidates {
+        let drawn: HashSet<usize> = draws.iter().copied().collect();
+
+        let others_win = bingo.boards.iter().enumerate().any(|(i, other)| {
+            let numbers: Vec<usize> = other.numbers().collect();
+
+            i != target
+                && layouts[&(other.width(), other.height())]
+                    .iter()
+                    .any(|pattern| pattern.iter().all(|&cell| drawn.contains(&numbers[cell])))
+        });
+
+        if !others_win {
+            let unmarked: usize = numbers
+                .iter()
+                .filter(|number| !drawn.contains(number))
+                .sum();
+            let last = *draws.last().expect("patterns are never empty");
+
+            return Ok(Some(Plan {
+                board: target,
+                score: unmarked * last,
+                draws,
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day4::{parse_bingo, Timeline};
+
+    #[test]
+    fn test_fastest_win() {
+        let bingo = parse_bingo(include_str!("../../fixtures/day4.txt")).unwrap();
+
+        for target in 0..bingo.boards.len() {
+            let plan = fastest_win(&bingo, &[WinRule::Lines], target)
+                .unwrap()
+                .unwrap();
+            assert_eq!(plan.draws.len(), 5);
+
+            let replayed = Bingo {
+                rounds: plan.draws.clone(),
+                ..bingo.clone()
+            };
+            let timeline = Timeline::new(&replayed, &[WinRule::Lines]).unwrap();
+            assert_eq!(timeline.first().map(|win| win.board), Some(target));
+            assert_eq!(timeline.first().map(|win| win.score), Some(plan.score));
+            assert_eq!(timeline.ranked().count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_impossible() {
+        // Every line of the second board is also a line of the first.
+        let bingo = parse_bingo("1,2,3,4\n\n1 2\n3 4\n\n1 2\n3 4\n").unwrap();
+
+        assert_eq!(fastest_win(&bingo, &[WinRule::Lines], 1).unwrap(), None);
+        assert!(fastest_win(&bingo, &[WinRule::Lines], 2).is_err());
+
+        let bingo = parse_bingo("1,2\n\n1 2\n3 4\n").unwrap();
+        assert_eq!(
+            fastest_win(&bingo, &[WinRule::Lines], 0).unwrap(),
+            Some(Plan {
+                board: 0,
+                draws: vec![1, 2],
+                score: 2 * 7
+            })
+        );
+    }
+}
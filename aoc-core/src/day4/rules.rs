@@ -0,0 +1,233 @@
+//! Ways a bingo board can win.
+//!
+//! Each rule compiles, for a given board size, to a list of patterns: sets
+//! of cells that win once all of them are marked. A board wins as soon as
+//! any pattern of any of its rules is complete.
+//!
+//! Masks are small ASCII pictures, `#` for a cell that must be marked and
+//! `.` for one that does not matter:
+//!
+//! ```text
+//! #.#
+//! .#.
+//! #.#
+//! ```
+//!
+//! A mask smaller than the board wins wherever it fits.
+
+use crate::parse::ParseError;
+use anyhow::{Error, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// Indices, row by row, of cells that win together.
+pub type Pattern = Vec<usize>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask {
+    width: usize,
+    height: usize,
+    cells: Vec<bool>,
+}
+
+impl Mask {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let rows: Vec<&str> = input.lines().map(str::trim_end).collect();
+        // Blank rows around the mask are padding, not part of it.
+        let first = rows.iter().position(|row| !row.is_empty()).unwrap_or(0);
+        let last = rows
+            .iter()
+            .rposition(|row| !row.is_empty())
+            .map_or(0, |last| last + 1);
+        let rows = &rows[first..last.max(first)];
+        let width = rows.first().map_or(0, |row| row.len());
+
+        let mut cells = Vec::with_capacity(width * rows.len());
+        for row in rows {
+            if let Some(pos) = row.find(|c| c != '#' && c != '.') {
+                return Err(ParseError::at(input, &row[pos..], "'#' or '.'"));
+            }
+
+            if row.len() != width {
+                let at = &row[row.len().min(width)..];
+                return Err(ParseError::at(
+                    input,
+                    at,
+                    format!("{} cells per mask row", width),
+                ));
+            }
+
+            cells.extend(row.bytes().map(|c| c == b'#'));
+        }
+
+        if !cells.contains(&true) {
+            return Err(ParseError::at(input, input, "a mask with at least one '#'"));
+        }
+
+        Ok(Mask {
+            width,
+            height: rows.len(),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+}
+
+impl FromStr for Mask {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        Mask::parse(s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WinRule {
+    /// Any complete row or column.
+    Lines,
+    /// Either main diagonal of a square board.
+    Diagonals,
+    /// All four corners.
+    Corners,
+    /// Every cell on the board.
+    Blackout,
+    /// A user-defined mask, anywhere it fits on the board.
+    Mask(Mask),
+}
+
+impl fmt::Display for WinRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WinRule::Lines => write!(f, "lines"),
+            WinRule::Diagonals => write!(f, "diagonals"),
+            WinRule::Corners => write!(f, "corners"),
+            WinRule::Blackout => write!(f, "blackout"),
+            WinRule::Mask(mask) => write!(f, "{}x{} mask", mask.width, mask.height),
+        }
+    }
+}
+
+impl WinRule {
+    /// The winning patterns of this rule on a `width` by `height` board.
+    pub fn patterns(&self, width: usize, height: usize) -> Result<Vec<Pattern>> {
+        let cell = |x: usize, y: usize| y * width + x;
+
+        match self {
+            WinRule::Lines => Ok((0..height)
+                .map(|y| (0..width).map(|x| cell(x, y)).collect())
+                .chain((0..width).map(|x| (0..height).map(|y| cell(x, y)).collect()))
+                .collect()),
+            WinRule::Diagonals if width != height => Err(Error::msg(format!(
+                "diagonals need a square board, not {}x{}",
+                width, height
+            ))),
+            WinRule::Diagonals => Ok(vec![
+                (0..width).map(|i| cell(i, i)).collect(),
+                (0..width).map(|i| cell(width - 1 - i, i)).collect(),
+            ]),
+            WinRule::Corners => {
+                let mut corners = vec![
+                    cell(0, 0),
+                    cell(width - 1, 0),
+                    cell(0, height - 1),
+                    cell(width - 1, height - 1),
+                ];
+                corners.sort_unstable();
+                corners.dedup();
+                Ok(vec![corners])
+            }
+            WinRule::Blackout => Ok(vec![(0..width * height).collect()]),
+            WinRule::Mask(mask) if mask.width > width || mask.height > height => {
+                Err(Error::msg(format!(
+                    "a {}x{} mask does not fit on a {}x{} board",
+                    mask.width, mask.height, width, height
+                )))
+            }
+            WinRule::Mask(mask) => {
+                let offsets = (0..=height - mask.height)
+                    .flat_map(|dy| (0..=width - mask.width).map(move |dx| (dx, dy)));
+
+                Ok(offsets
+                    .map(|(dx, dy)| {
+                        mask.cells
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, &marked)| marked)
+                            .map(|(i, _)| cell(dx + i % mask.width, dy + i / mask.width))
+                            .collect()
+                    })
+                    .collect())
+            }
+        }
+    }
+}
+
+/// Every winning pattern of `rules` on a `width` by `height` board.
+pub fn compile(rules: &[WinRule], width: usize, height: usize) -> Result<Vec<Pattern>> {
+    let mut patterns = Vec::new();
+
+    for rule in rules {
+        patterns.extend(rule.patterns(width, height)?);
+    }
+
+    Ok(patterns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patterns() {
+        assert_eq!(
+            WinRule::Lines.patterns(2, 3).unwrap(),
+            [
+                vec![0, 1],
+                vec![2, 3],
+                vec![4, 5],
+                vec![0, 2, 4],
+                vec![1, 3, 5]
+            ]
+        );
+        assert_eq!(
+            WinRule::Diagonals.patterns(3, 3).unwrap(),
+            [vec![0, 4, 8], vec![2, 4, 6]]
+        );
+        assert!(WinRule::Diagonals.patterns(3, 2).is_err());
+        assert_eq!(WinRule::Corners.patterns(3, 2).unwrap(), [vec![0, 2, 3, 5]]);
+
+        let mask = Mask::parse("#.\n.#\n").unwrap();
+        assert_eq!(Mask::parse("\n\n#.\n.#\n\n").unwrap(), mask);
+        assert_eq!(
+            WinRule::Mask(mask).patterns(3, 2).unwrap(),
+            [vec![0, 4], vec![1, 5]]
+        );
+    }
+
+    #[test]
+    fn test_mask_errors() {
+        assert_eq!(
+            Mask::parse("#.\n.x\n").unwrap_err().to_string(),
+            "line 2, col 2: expected '#' or '.'"
+        );
+        assert_eq!(
+            Mask::parse("#.\n.#.\n").unwrap_err().to_string(),
+            "line 2, col 3: expected 2 cells per mask row"
+        );
+        assert_eq!(
+            Mask::parse("\n#.\n\n.#\n").unwrap_err().to_string(),
+            "line 3, col 1: expected 2 cells per mask row"
+        );
+        assert_eq!(
+            Mask::parse("..\n").unwrap_err().to_string(),
+            "line 1, col 1: expected a mask with at least one '#'"
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use crate::arith::{self, OverflowError};
+use crate::parse::{self, ParseError, Parsed};
+use crate::solver::{Answer, Solver};
+use anyhow::Result;
+use nom::{bytes::complete as bytes, character::complete as character, combinator, sequence};
+
+const DAY: u8 = 5;
+
+#[derive(Debug)]
+pub struct Point(pub usize, pub usize);
+
+#[derive(Debug)]
+pub struct FromTo(pub Point, pub Point);
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DiagDir {
+    LeftRight,
+    RightLeft,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Line {
+    Vert {
+        x: usize,
+        y1: usize,
+        y2: usize,
+    },
+    Horiz {
+        y: usize,
+        x1: usize,
+        x2: usize,
+    },
+    Diag {
+        x_left: usize,
+        y_left: usize,
+        length: usize,
+        dir: DiagDir,
+    },
+}
+
+impl From<&FromTo> for Line {
+    fn from(from_to: &FromTo) -> Line {
+        let FromTo(from, to) = from_to;
+
+        match (from.0 == to.0, from.1 == to.1) {
+            (true, _) => Line::Vert {
+                x: from.0,
+                y1: from.1.min(to.1),
+                y2: from.1.max(to.1),
+            },
+            (_, true) => Line::Horiz {
+                y: from.1,
+                x1: from.0.min(to.0),
+                x2: from.0.max(to.0),
+            },
+            _ => {
+                let (left_point, right_point) = if from.0 < to.0 {
+                    (from, to)
+                } else {
+                    (to, from)
+                };
+                Line::Diag {
+                    x_left: left_point.0,
+                    y_left: left_point.1,
+                    length: right_point.0 - left_point.0 + 1,
+                    dir: if left_point.1 < right_point.1 {
+                        DiagDir::LeftRight
+                    } else {
+                        DiagDir::RightLeft
+                    },
+                }
+            }
+        }
+    }
+}
+
+impl Line {
+    pub fn is_diag(&self) -> bool {
+        matches!(self, Line::Diag { .. })
+    }
+}
+
+#[derive(Debug)]
+pub struct Rectangle {
+    offset: (usize, usize),
+    size: (usize, usize),
+    cells: Vec<usize>,
+}
+
+impl Rectangle {
+    pub fn new(offset: (usize, usize), size: (usize, usize)) -> Self {
+        Self {
+            offset,
+            size,
+            cells: vec![0; size.0 * size.1],
+        }
+    }
+
+    pub fn bounding(from_tos: &[FromTo]) -> Self {
+        let (min_x, min_y, max_x, max_y) = from_tos
+            .iter()
+            .flat_map(|from_to| [&from_to.0, &from_to.1])
+            .fold(
+                (usize::MAX, usize::MAX, usize::MIN, usize::MIN),
+                |(min_x, min_y, max_x, max_y), &Point(x, y)| {
+                    (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+                },
+            );
+
+        Self::new((min_x, min_y), (max_x - min_x + 1, max_y - min_y + 1))
+    }
+
+    pub fn add_point(&mut self, x: usize, y: usize) {
+        let (local_x, local_y) = (x - self.offset.0, y - self.offset.1);
+
+        self.cells[local_y * self.size.0 + local_x] += 1;
+    }
+
+    pub fn add_line(&mut self, line: &Line) -> Result<(), OverflowError> {
+        match *line {
+            Line::Vert { x, y1, y2 } => {
+                for y in y1..=y2 {
+                    self.add_point(x, y)
+                }
+            }
+            Line::Horiz { y, x1, x2 } => {
+                for x in x1..=x2 {
+                    self.add_point(x, y)
+                }
+            }
+            Line::Diag {
+                dir: DiagDir::LeftRight,
+                x_left,
+                y_left,
+                length,
+            } => {
+                for (x, y) in
+                    itertools::iterate((x_left, y_left), |(x, y)| (x + 1, y + 1)).take(length)
+                {
+                    self.add_point(x, y);
+                }
+            }
+            Line::Diag {
+                dir: DiagDir::RightLeft,
+                x_left,
+                y_left,
+                length,
+            } => {
+                for i in 0..length {
+                    let y = arith::sub(DAY, "y coordinate of a right-left diagonal", y_left, i)?;
+                    self.add_point(x_left + i, y);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn intersect_count(&self) -> usize {
+        self.cells
+            .iter()
+            .filter(|&&passing_lines| passing_lines > 1)
+            .count()
+    }
+}
+
+fn parse_point(input: &str) -> Parsed<'_, Point> {
+    combinator::map(
+        sequence::separated_pair(
+            parse::context("x coordinate", parse::number),
+            parse::context("',' after x coordinate", character::char(',')),
+            parse::context("y coordinate", parse::number),
+        ),
+        |(x, y)| Point(x, y),
+    )(input)
+}
+
+fn parse_from_to(input: &str) -> Parsed<'_, FromTo> {
+    parse::context(
+        "vent segment",
+        combinator::map(
+            sequence::separated_pair(
+                parse_point,
+                parse::context("' -> ' between endpoints", bytes::tag(" -> ")),
+                parse_point,
+            ),
+            |(p1, p2)| FromTo(p1, p2),
+        ),
+    )(input)
+}
+
+pub fn parse_vents(input: &str) -> Result<Vec<FromTo>, ParseError> {
+    let from_tos = parse::lines(input, parse_from_to)?;
+
+    if from_tos.is_empty() {
+        return Err(ParseError::new(1, 1, "a vent segment"));
+    }
+
+    Ok(from_tos)
+}
+
+fn overlaps(from_tos: &[FromTo], with_diags: bool) -> Result<usize, OverflowError> {
+    let mut rect = Rectangle::bounding(from_tos);
+    let lines = from_tos
+        .iter()
+        .map(Line::from)
+        .filter(|line| with_diags || !line.is_diag());
+
+    for line in lines {
+        rect.add_line(&line)?;
+    }
+
+    Ok(rect.intersect_count())
+}
+
+pub fn part1(from_tos: &[FromTo]) -> Result<usize, OverflowError> {
+    overlaps(from_tos, false)
+}
+
+pub fn part2(from_tos: &[FromTo]) -> Result<usize, OverflowError> {
+    overlaps(from_tos, true)
+}
+
+pub struct HydrothermalVenture;
+
+impl Solver for HydrothermalVenture {
+    type Input = Vec<FromTo>;
+
+    const DAY: u8 = DAY;
+    const NAME: &'static str = "Hydrothermal Venture";
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        Ok(parse_vents(input)?)
+    }
+
+    fn part1(&self, from_tos: &Self::Input) -> Result<Answer> {
+        Ok(part1(from_tos)?.into())
+    }
+
+    fn part2(&self, from_tos: &Self::Input) -> Result<Answer> {
+        Ok(part2(from_tos)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::assert_example;
+
+    #[test]
+    fn test_diags() {
+        let diag = FromTo(Point(3, 3), Point(1, 1));
+
+        assert_eq!(
+            Line::from(&diag),
+            Line::Diag {
+                x_left: 1,
+                y_left: 1,
+                length: 3,
+                dir: DiagDir::LeftRight
+            }
+        );
+    }
+
+    #[test]
+    fn test_example() {
+        assert_example(
+            HydrothermalVenture,
+            include_str!("../fixtures/day5.txt"),
+            Answer::Number(5),
+            Answer::Number(12),
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            parse_vents("0,9 -> 5,9\n8,0 -> 0,8\n9 4 -> 3,4\n")
+                .unwrap_err()
+                .to_string(),
+            "line 3, col 2: expected ',' after x coordinate in vent segment"
+        );
+        assert_eq!(
+            parse_vents("\n").unwrap_err().to_string(),
+            "line 1, col 1: expected a vent segment"
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use crate::arith::{self, OverflowError};
+use crate::parse::ParseError;
+use crate::scan::Numbers;
+use crate::solver::{Answer, Solver};
+use anyhow::Result;
+
+const DAY: u8 = 6;
+
+pub type School = [usize; 9];
+
+pub fn parse_school(input: &str) -> Result<School> {
+    let mut ages = Numbers::comma_separated(input.as_bytes());
+    let mut fishes = [0; 9];
+
+    while let Some(age) = ages.next() {
+        let age = age?;
+
+        if age >= fishes.len() {
+            let (line, col) = ages.position();
+            return Err(ParseError::new(line, col, "a timer between 0 and 8").into());
+        }
+
+        fishes[age] += 1;
+    }
+
+    Ok(fishes)
+}
+
+pub fn simulate(initial_state: &School, days: usize) -> Result<usize, OverflowError> {
+    let mut state = *initial_state;
+
+    for _ in 0..days {
+        state.rotate_left(1);
+        state[6] = arith::add(DAY, "fish with a 6-day timer", state[6], state[8])?;
+    }
+
+    arith::sum(DAY, "total fish count", 0, state)
+}
+
+pub fn part1(school: &School) -> Result<usize, OverflowError> {
+    simulate(school, 80)
+}
+
+pub fn part2(school: &School) -> Result<usize, OverflowError> {
+    simulate(school, 256)
+}
+
+pub struct Lanternfish;
+
+impl Solver for Lanternfish {
+    type Input = School;
+
+    const DAY: u8 = DAY;
+    const NAME: &'static str = "Lanternfish";
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_school(input)
+    }
+
+    fn part1(&self, school: &Self::Input) -> Result<Answer> {
+        Ok(part1(school)?.into())
+    }
+
+    fn part2(&self, school: &Self::Input) -> Result<Answer> {
+        Ok(part2(school)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::assert_example;
+
+    #[test]
+    fn test_example() {
+        assert_example(
+            Lanternfish,
+            include_str!("../fixtures/day6.txt"),
+            Answer::Number(5934),
+            Answer::Number(26984457539),
+        );
+    }
+}
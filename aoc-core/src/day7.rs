@@ -0,0 +1,97 @@
+use crate::arith::{self, OverflowError};
+use crate::parse::ParseError;
+use crate::scan::Numbers;
+use crate::solver::{Answer, Solver};
+use anyhow::Result;
+
+const DAY: u8 = 7;
+
+pub fn parse_crabs(input: &str) -> Result<Vec<usize>> {
+    let crabs = Numbers::comma_separated(input.as_bytes()).collect::<Result<Vec<_>>>()?;
+
+    if crabs.is_empty() {
+        return Err(ParseError::new(1, 1, "a crab position").into());
+    }
+
+    Ok(crabs)
+}
+
+fn distance(pos: usize, target: usize) -> Result<usize, OverflowError> {
+    let pos = arith::to_isize(DAY, "crab position", pos)?;
+    let target = arith::to_isize(DAY, "alignment position", target)?;
+
+    Ok(arith::sub(DAY, "distance to the alignment position", pos, target)?.unsigned_abs())
+}
+
+pub fn part1(crabs: &[usize]) -> Result<usize, OverflowError> {
+    let sorted_crabs = {
+        let mut crabs = crabs.to_vec();
+        crabs.sort_unstable();
+        crabs
+    };
+
+    let median = sorted_crabs[sorted_crabs.len() / 2];
+
+    let distances = sorted_crabs
+        .into_iter()
+        .map(|pos| distance(pos, median))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    arith::sum(DAY, "total fuel", 0, distances)
+}
+
+fn triangular_fuel(crabs: &[usize], target: usize) -> Result<usize, OverflowError> {
+    let costs = crabs
+        .iter()
+        .map(|&pos| {
+            let dist = distance(pos, target)?;
+            Ok(arith::mul(DAY, "triangular fuel cost", dist, dist + 1)? / 2)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    arith::sum(DAY, "total fuel", 0, costs)
+}
+
+pub fn part2(crabs: &[usize]) -> Result<usize, OverflowError> {
+    // The optimum lies within 0.5 of the mean, so try both integers around it.
+    let mean = arith::sum(DAY, "sum of crab positions", 0, crabs.iter().copied())? / crabs.len();
+
+    Ok(triangular_fuel(crabs, mean)?.min(triangular_fuel(crabs, mean + 1)?))
+}
+
+pub struct TreacheryOfWhales;
+
+impl Solver for TreacheryOfWhales {
+    type Input = Vec<usize>;
+
+    const DAY: u8 = DAY;
+    const NAME: &'static str = "The Treachery of Whales";
+
+    fn parse(&self, input: &str) -> Result<Self::Input> {
+        parse_crabs(input)
+    }
+
+    fn part1(&self, crabs: &Self::Input) -> Result<Answer> {
+        Ok(part1(crabs)?.into())
+    }
+
+    fn part2(&self, crabs: &Self::Input) -> Result<Answer> {
+        Ok(part2(crabs)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::assert_example;
+
+    #[test]
+    fn test_example() {
+        assert_example(
+            TreacheryOfWhales,
+            include_str!("../fixtures/day7.txt"),
+            Answer::Number(37),
+            Answer::Number(168),
+        );
+    }
+}
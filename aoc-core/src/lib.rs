@@ -0,0 +1,32 @@
+pub mod answers;
+pub mod arith;
+pub mod bits;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod parse;
+pub mod scan;
+pub mod solver;
+pub mod window;
+
+use solver::Puzzle;
+
+pub fn puzzles() -> Vec<Box<dyn Puzzle>> {
+    vec![
+        Box::new(day1::SonarSweep),
+        Box::new(day2::Dive),
+        Box::new(day3::BinaryDiagnostic),
+        Box::new(day4::GiantSquid),
+        Box::new(day5::HydrothermalVenture),
+        Box::new(day6::Lanternfish),
+        Box::new(day7::TreacheryOfWhales),
+    ]
+}
+
+pub fn puzzle(day: u8) -> Option<Box<dyn Puzzle>> {
+    puzzles().into_iter().find(|puzzle| puzzle.day() == day)
+}
@@ -0,0 +1,181 @@
+use nom::{
+    branch,
+    character::complete as character,
+    combinator,
+    error::{ErrorKind, VerboseError, VerboseErrorKind},
+    sequence, Err, IResult,
+};
+use std::fmt;
+
+pub type Parsed<'a, T> = IResult<&'a str, T, VerboseError<&'a str>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub col: usize,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, col: usize, expected: impl Into<String>) -> Self {
+        Self {
+            line,
+            col,
+            expected: expected.into(),
+        }
+    }
+
+    /// Builds an error pointing at `rest`, which must be a slice of `input`.
+    pub fn at(input: &str, rest: &str, expected: impl Into<String>) -> Self {
+        let offset = (rest.as_ptr() as usize - input.as_ptr() as usize).min(input.len());
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |pos| pos + 1);
+
+        Self::new(
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+            expected,
+        )
+    }
+}
+
+/// Maps slices of an input back to their line numbers.
+pub struct LineIndex<'a> {
+    input: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(input: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(input.match_indices('\n').map(|(pos, _)| pos + 1))
+            .collect();
+
+        Self { input, line_starts }
+    }
+
+    /// Line of the start of `rest`, which must be a slice of the indexed input.
+    pub fn line(&self, rest: &str) -> usize {
+        let offset = rest.as_ptr() as usize - self.input.as_ptr() as usize;
+
+        match self.line_starts.binary_search(&offset) {
+            Ok(line) => line + 1,
+            Err(line) => line,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, col {}: expected {}",
+            self.line, self.col, self.expected
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn describe(kind: &VerboseErrorKind) -> String {
+    match kind {
+        VerboseErrorKind::Context(context) => context.to_string(),
+        VerboseErrorKind::Char(c) => format!("'{}'", c),
+        VerboseErrorKind::Nom(ErrorKind::Digit) => "a number".to_string(),
+        VerboseErrorKind::Nom(ErrorKind::Eof) => "end of input".to_string(),
+        VerboseErrorKind::Nom(ErrorKind::CrLf) => "end of line".to_string(),
+        VerboseErrorKind::Nom(kind) => kind.description().to_lowercase(),
+    }
+}
+
+/// Turns a nom result into a `ParseError` located in `input`.
+///
+/// The innermost context names what was expected, and every enclosing
+/// context is appended, e.g. "',' after x coordinate in vent segment".
+pub fn finish<'a, T>(input: &'a str, result: Parsed<'a, T>) -> Result<T, ParseError> {
+    match result {
+        Ok((_, value)) => Ok(value),
+        Err(Err::Error(e)) | Err(Err::Failure(e)) => {
+            let rest = e.errors.first().map_or(input, |(rest, _)| *rest);
+            let mut contexts = e.errors.iter().filter_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(context) => Some(*context),
+                _ => None,
+            });
+            let expected = match contexts.next() {
+                Some(context) => {
+                    contexts.fold(context.to_string(), |acc, outer| acc + " in " + outer)
+                }
+                None => e
+                    .errors
+                    .first()
+                    .map_or("valid input".to_string(), |(_, kind)| describe(kind)),
+            };
+
+            Err(ParseError::at(input, rest, expected))
+        }
+        Err(Err::Incomplete(_)) => Err(ParseError::at(input, &input[input.len()..], "more input")),
+    }
+}
+
+pub fn context<'a, T>(
+    context: &'static str,
+    parser: impl FnMut(&'a str) -> Parsed<'a, T>,
+) -> impl FnMut(&'a str) -> Parsed<'a, T> {
+    nom::error::context(context, parser)
+}
+
+pub fn number(input: &str) -> Parsed<'_, usize> {
+    combinator::map_res(character::digit1, |n: &str| n.parse::<usize>())(input)
+}
+
+/// Succeeds, without consuming the line ending, when only spaces remain on the line.
+pub fn end_of_line(input: &str) -> Parsed<'_, ()> {
+    combinator::value(
+        (),
+        sequence::pair(
+            character::space0,
+            combinator::peek(branch::alt((character::line_ending, combinator::eof))),
+        ),
+    )(input)
+}
+
+/// Parses every non-blank line of `input` with `parser`, which must consume the line.
+pub fn lines<'a, T>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> Parsed<'a, T>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            finish(
+                input,
+                sequence::terminated(&mut parser, context("end of line", end_of_line))(line),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position() {
+        let input = "1,2\n3;4\n";
+
+        assert_eq!(
+            ParseError::at(input, &input[5..], "','"),
+            ParseError::new(2, 2, "','")
+        );
+        assert_eq!(
+            lines(input, |line| nom::multi::separated_list1(
+                character::char(','),
+                number
+            )(line))
+            .unwrap_err()
+            .to_string(),
+            "line 2, col 2: expected end of line"
+        );
+    }
+}
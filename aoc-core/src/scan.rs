@@ -0,0 +1,227 @@
+use crate::parse::ParseError;
+use anyhow::Result;
+use std::io::{BufRead, ErrorKind};
+
+/// Streaming scanner for separator-delimited unsigned integers.
+///
+/// Works straight on the reader's buffer without allocating, tracks the
+/// line and column for error reporting, and ignores spaces, tabs, carriage
+/// returns and (unless they are the separator) line feeds around numbers.
+/// Newline-separated streams may contain blank lines.
+pub struct Numbers<R> {
+    reader: R,
+    separator: u8,
+    offset: usize,
+    line: usize,
+    line_start: usize,
+    start: (usize, usize),
+    value: Option<usize>,
+    after_number: bool,
+    after_separator: bool,
+    done: bool,
+}
+
+impl<R: BufRead> Numbers<R> {
+    pub fn new(reader: R, separator: u8) -> Self {
+        Self {
+            reader,
+            separator,
+            offset: 0,
+            line: 1,
+            line_start: 0,
+            start: (1, 1),
+            value: None,
+            after_number: false,
+            after_separator: false,
+            done: false,
+        }
+    }
+
+    /// One number per line.
+    pub fn lines(reader: R) -> Self {
+        Self::new(reader, b'\n')
+    }
+
+    /// Numbers separated by commas.
+    pub fn comma_separated(reader: R) -> Self {
+        Self::new(reader, b',')
+    }
+
+    /// Line and column where the last returned number started.
+    pub fn position(&self) -> (usize, usize) {
+        self.start
+    }
+
+    fn expected_separator(&self) -> &'static str {
+        if self.separator == b'\n' {
+            "end of line"
+        } else {
+            "','"
+        }
+    }
+
+    fn fail(&mut self, consumed: usize, expected: &'static str) -> Option<Result<usize>> {
+        self.reader.consume(consumed);
+        self.offset += consumed;
+        self.done = true;
+
+        let col = self.offset - self.line_start + 1;
+        Some(Err(ParseError::new(self.line, col, expected).into()))
+    }
+}
+
+impl<R: BufRead> Iterator for Numbers<R> {
+    type Item = Result<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        loop {
+            let buf = match self.reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e.into()));
+                }
+            };
+
+            if buf.is_empty() {
+                return match self.value.take() {
+                    Some(value) => {
+                        self.done = true;
+                        Some(Ok(value))
+                    }
+                    None if self.after_separator => self.fail(0, "a number"),
+                    None => {
+                        self.done = true;
+                        None
+                    }
+                };
+            }
+
+            let separator = self.separator;
+            let mut i = 0;
+
+            while i < buf.len() {
+                let byte = buf[i];
+
+                if byte.is_ascii_digit() {
+                    let mut value = match self.value {
+                        Some(value) => value,
+                        None if self.after_number => {
+                            let expected = self.expected_separator();
+                            return self.fail(i, expected);
+                        }
+                        None => {
+                            self.start = (self.line, self.offset + i - self.line_start + 1);
+                            self.after_separator = false;
+                            0
+                        }
+                    };
+
+                    while i < buf.len() && buf[i].is_ascii_digit() {
+                        value = match value
+                            .checked_mul(10)
+                            .and_then(|value| value.checked_add((buf[i] - b'0') as usize))
+                        {
+                            Some(value) => value,
+                            None => return self.fail(i, "a number that fits in 64 bits"),
+                        };
+                        i += 1;
+                    }
+
+                    self.value = Some(value);
+                    continue;
+                }
+
+                let found = match byte {
+                    _ if byte == separator => {
+                        let found = self.value.take();
+                        if found.is_none() && !self.after_number && separator != b'\n' {
+                            return self.fail(i, "a number");
+                        }
+                        self.after_number = false;
+                        self.after_separator = separator != b'\n';
+                        found
+                    }
+                    b' ' | b'\t' | b'\r' | b'\n' => {
+                        let found = self.value.take();
+                        self.after_number |= found.is_some();
+                        found
+                    }
+                    _ => {
+                        let expected = if self.value.is_some() || self.after_number {
+                            self.expected_separator()
+                        } else {
+                            "a number"
+                        };
+                        return self.fail(i, expected);
+                    }
+                };
+
+                i += 1;
+                if byte == b'\n' {
+                    self.line += 1;
+                    self.line_start = self.offset + i;
+                }
+
+                if found.is_some() {
+                    self.reader.consume(i);
+                    self.offset += i;
+                    return found.map(Ok);
+                }
+            }
+
+            let consumed = buf.len();
+            self.reader.consume(consumed);
+            self.offset += consumed;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let numbers = Numbers::lines("12\n\n  7 \r\n300\n\n".as_bytes())
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(numbers, vec![12, 7, 300]);
+        assert_eq!(
+            Numbers::lines("12\n7 8\n".as_bytes())
+                .collect::<Result<Vec<_>>>()
+                .unwrap_err()
+                .to_string(),
+            "line 2, col 3: expected end of line"
+        );
+    }
+
+    #[test]
+    fn test_comma_separated() {
+        let numbers = Numbers::comma_separated("3, 4,5\n".as_bytes())
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(numbers, vec![3, 4, 5]);
+        assert_eq!(
+            Numbers::comma_separated("3,,4".as_bytes())
+                .collect::<Result<Vec<_>>>()
+                .unwrap_err()
+                .to_string(),
+            "line 1, col 3: expected a number"
+        );
+        assert_eq!(
+            Numbers::comma_separated("3,4,".as_bytes())
+                .collect::<Result<Vec<_>>>()
+                .unwrap_err()
+                .to_string(),
+            "line 1, col 5: expected a number"
+        );
+    }
+}
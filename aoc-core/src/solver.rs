@@ -0,0 +1,115 @@
+use anyhow::{Error, Result};
+use num_bigint::BigUint;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    /// An answer too large for `Number`.
+    Big(BigUint),
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Number(value as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Answer::Number(value as i128)
+    }
+}
+
+impl From<BigUint> for Answer {
+    fn from(value: BigUint) -> Self {
+        match i128::try_from(&value) {
+            Ok(value) => Answer::Number(value),
+            Err(_) => Answer::Big(value),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(value) => write!(f, "{}", value),
+            Answer::Big(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// A day's solution, split so the parsed input can be shared by both parts.
+pub trait Solver {
+    type Input;
+
+    const DAY: u8;
+    const NAME: &'static str;
+
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+    fn part1(&self, input: &Self::Input) -> Result<Answer>;
+    fn part2(&self, input: &Self::Input) -> Result<Answer>;
+}
+
+pub struct PartRun {
+    pub part: u8,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+pub struct Run {
+    pub parse_time: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+/// Object-safe view of a `Solver`, used by the registry.
+pub trait Puzzle {
+    fn day(&self) -> u8;
+    fn name(&self) -> &'static str;
+    fn run(&self, input: &str, parts: &[u8]) -> Result<Run>;
+}
+
+impl<S: Solver> Puzzle for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn name(&self) -> &'static str {
+        S::NAME
+    }
+
+    fn run(&self, input: &str, parts: &[u8]) -> Result<Run> {
+        let start = Instant::now();
+        let parsed = self.parse(input)?;
+        let parse_time = start.elapsed();
+
+        let parts = parts
+            .iter()
+            .map(|&part| {
+                let start = Instant::now();
+                let answer = match part {
+                    1 => self.part1(&parsed),
+                    2 => self.part2(&parsed),
+                    _ => Err(Error::msg(format!("unknown part: {}", part))),
+                }?;
+
+                Ok(PartRun {
+                    part,
+                    answer,
+                    elapsed: start.elapsed(),
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Run { parse_time, parts })
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn assert_example<S: Solver>(solver: S, input: &str, part1: Answer, part2: Answer) {
+    let parsed = solver.parse(input).unwrap();
+
+    assert_eq!(solver.part1(&parsed).unwrap(), part1);
+    assert_eq!(solver.part2(&parsed).unwrap(), part2);
+}
@@ -0,0 +1,172 @@
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Sum,
+    Min,
+    Max,
+    Mean,
+}
+
+impl FromStr for Aggregate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sum" => Ok(Aggregate::Sum),
+            "min" => Ok(Aggregate::Min),
+            "max" => Ok(Aggregate::Max),
+            "mean" => Ok(Aggregate::Mean),
+            _ => Err(format!(
+                "unknown aggregate '{}', expected sum, min, max or mean",
+                s
+            )),
+        }
+    }
+}
+
+/// Fixed-width sliding window over a stream of values.
+///
+/// Sum is kept incrementally and min/max through monotonic queues, so every
+/// aggregate is O(1) per slide whatever the width.
+#[derive(Debug, Clone)]
+pub struct Window {
+    width: usize,
+    values: VecDeque<usize>,
+    sum: usize,
+    mins: VecDeque<usize>,
+    maxs: VecDeque<usize>,
+}
+
+impl Window {
+    pub fn new(width: usize) -> Self {
+        assert!(width > 0, "window width must be positive");
+
+        Self {
+            width,
+            values: VecDeque::with_capacity(width),
+            sum: 0,
+            mins: VecDeque::new(),
+            maxs: VecDeque::new(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.values.len() == self.width
+    }
+
+    /// Pushes a value, returning the one that slid out of a full window.
+    pub fn slide(&mut self, value: usize) -> Option<usize> {
+        let evicted = if self.is_full() {
+            self.values.pop_front()
+        } else {
+            None
+        };
+
+        if let Some(evicted) = evicted {
+            self.sum -= evicted;
+            if self.mins.front() == Some(&evicted) {
+                self.mins.pop_front();
+            }
+            if self.maxs.front() == Some(&evicted) {
+                self.maxs.pop_front();
+            }
+        }
+
+        while self.mins.back().is_some_and(|&min| min > value) {
+            self.mins.pop_back();
+        }
+        while self.maxs.back().is_some_and(|&max| max < value) {
+            self.maxs.pop_back();
+        }
+
+        self.values.push_back(value);
+        self.mins.push_back(value);
+        self.maxs.push_back(value);
+        self.sum += value;
+
+        evicted
+    }
+
+    pub fn sum(&self) -> usize {
+        self.sum
+    }
+
+    pub fn min(&self) -> Option<usize> {
+        self.mins.front().copied()
+    }
+
+    pub fn max(&self) -> Option<usize> {
+        self.maxs.front().copied()
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        if self.values.is_empty() {
+            None
+        } else {
+            Some(self.sum as f64 / self.values.len() as f64)
+        }
+    }
+
+    /// Value used to compare two full windows: the mean orders like the sum
+    /// when the width is fixed, which keeps comparisons exact.
+    pub fn key(&self, aggregate: Aggregate) -> usize {
+        match aggregate {
+            Aggregate::Sum | Aggregate::Mean => self.sum,
+            Aggregate::Min => self.min().unwrap_or(0),
+            Aggregate::Max => self.max().unwrap_or(0),
+        }
+    }
+}
+
+/// Counts how many times the aggregate of a full window exceeds the previous one.
+pub fn increases(
+    values: impl IntoIterator<Item = usize>,
+    width: usize,
+    aggregate: Aggregate,
+) -> usize {
+    let mut window = Window::new(width);
+    let mut previous_key = None;
+    let mut increases = 0;
+
+    for value in values {
+        window.slide(value);
+
+        if !window.is_full() {
+            continue;
+        }
+
+        let key = window.key(aggregate);
+        if previous_key.is_some_and(|previous_key| key > previous_key) {
+            increases += 1;
+        }
+
+        previous_key = Some(key);
+    }
+
+    increases
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window() {
+        let mut window = Window::new(3);
+
+        for value in [5, 1, 4, 2, 8] {
+            window.slide(value);
+        }
+
+        assert_eq!(window.sum(), 14);
+        assert_eq!(window.min(), Some(2));
+        assert_eq!(window.max(), Some(8));
+        assert_eq!(increases([1, 3, 2, 4, 3, 5], 2, Aggregate::Min), 2);
+    }
+}
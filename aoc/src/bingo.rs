@@ -0,0 +1,338 @@
+use crate::replay::Replay;
+use crate::{read_input, Format};
+use anyhow::{Error, Result};
+use aoc_core::day4::generate::{self, Spec};
+use aoc_core::day4::odds::{self, Odds};
+use aoc_core::day4::{self, optimize, Mask, Timeline, WinRule};
+use clap::{Args, Subcommand, ValueEnum};
+use crossterm::terminal;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum RuleArg {
+    /// Any complete row or column
+    Lines,
+    /// Either main diagonal of a square board
+    Diagonals,
+    /// All four corners
+    Corners,
+    /// Every cell on the board
+    Blackout,
+}
+
+#[derive(Args)]
+pub struct RuleArgs {
+    /// Win rule, may be repeated; defaults to lines when no rule or mask is given
+    #[arg(short, long, value_enum)]
+    rule: Vec<RuleArg>,
+    /// ASCII pattern file of `#` and `.` that wins wherever it fits, may be repeated
+    #[arg(short, long)]
+    mask: Vec<PathBuf>,
+}
+
+#[derive(Subcommand)]
+pub enum BingoCommand {
+    /// Score the first (or last) board to win under custom rules
+    Play {
+        #[command(flatten)]
+        rules: RuleArgs,
+        /// Score the last board to win instead of the first
+        #[arg(long)]
+        last: bool,
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+    /// When every board wins, in winning order
+    Timeline {
+        #[command(flatten)]
+        rules: RuleArgs,
+        /// Only print the score of the k-th board to win
+        #[arg(short, long)]
+        kth: Option<usize>,
+        #[arg(short, long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+    /// Replay the draws on every board, interactively in the terminal
+    Replay {
+        #[command(flatten)]
+        rules: RuleArgs,
+        /// Print the boards after this many draws and exit instead
+        #[arg(long)]
+        at: Option<usize>,
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+    /// Find the shortest subsequence of the draws that makes one board win first
+    Optimize {
+        /// Board to make win, counting from 1
+        board: usize,
+        #[command(flatten)]
+        rules: RuleArgs,
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+    /// Print a random input in the puzzle format
+    Generate {
+        /// Seed for the random number generator
+        #[arg(short, long, default_value_t = 0)]
+        seed: u64,
+        #[arg(short, long, default_value_t = 100)]
+        boards: usize,
+        #[arg(long, default_value_t = 5)]
+        width: usize,
+        #[arg(long, default_value_t = 5)]
+        height: usize,
+        /// Numbers range from 0 to this, exclusive
+        #[arg(short, long, default_value_t = 100)]
+        numbers: usize,
+        /// How many numbers to draw, all of them by default
+        #[arg(short, long)]
+        draws: Option<usize>,
+        /// Only accept games where every board wins and the first and last
+        /// winners are unique
+        #[arg(short, long)]
+        unique: bool,
+        #[command(flatten)]
+        rules: RuleArgs,
+    },
+    /// Odds of each board winning first when the draws are shuffled
+    Odds {
+        /// Number of random shuffles to estimate from
+        #[arg(short, long, default_value_t = 100_000)]
+        trials: usize,
+        /// Seed for the random number generator
+        #[arg(short, long, default_value_t = 0)]
+        seed: u64,
+        /// Worker threads, all available cores by default
+        #[arg(long)]
+        threads: Option<usize>,
+        /// Enumerate every draw order instead, for at most 20 draws
+        #[arg(short, long)]
+        exact: bool,
+        #[command(flatten)]
+        rules: RuleArgs,
+        #[arg(short, long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+}
+
+/// Win rules from the command line, lines when none are given.
+pub fn win_rules(args: &RuleArgs) -> Result<Vec<WinRule>> {
+    let mut win_rules: Vec<WinRule> = args
+        .rule
+        .iter()
+        .map(|rule| match rule {
+            RuleArg::Lines => WinRule::Lines,
+            RuleArg::Diagonals => WinRule::Diagonals,
+            RuleArg::Corners => WinRule::Corners,
+            RuleArg::Blackout => WinRule::Blackout,
+        })
+        .collect();
+
+    for path in &args.mask {
+        let mask = Mask::parse(&fs::read_to_string(path)?)
+            .map_err(|e| Error::msg(format!("{}: {}", path.display(), e)))?;
+        win_rules.push(WinRule::Mask(mask));
+    }
+
+    if win_rules.is_empty() {
+        win_rules.push(WinRule::Lines);
+    }
+
+    Ok(win_rules)
+}
+
+fn print_timeline(timeline: &Timeline) {
+    println!(
+        "{:>5} {:>6} {:>6} {:>7} {:>8}",
+        "rank", "board", "round", "number", "score"
+    );
+    for (rank, win) in timeline.ranked().enumerate() {
+        println!(
+            "{:>5} {:>6} {:>6} {:>7} {:>8}",
+            rank + 1,
+            win.board + 1,
+            win.round,
+            win.number,
+            win.score
+        );
+    }
+    for board in timeline.never() {
+        println!("{:>5} {:>6} {:>6}", "-", board + 1, "never");
+    }
+}
+
+fn print_odds(odds: &Odds) {
+    match odds.trials {
+        Some(trials) => println!("estimated from {} shuffles", trials),
+        None => println!("exact over every draw order"),
+    }
+    println!(
+        "{:>6} {:>8} {:>8} {:>11} {:>11}",
+        "board", "first", "never", "mean round", "likeliest"
+    );
+    for board in &odds.boards {
+        let likeliest = (1..)
+            .zip(&board.rounds)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .filter(|(_, p)| **p > 0.0)
+            .map_or("-".to_string(), |(round, _)| round.to_string());
+
+        println!(
+            "{:>6} {:>8.4} {:>8.4} {:>11} {:>11}",
+            board.board + 1,
+            board.first,
+            board.never,
+            board
+                .mean_round()
+                .map_or("-".to_string(), |mean| format!("{:.2}", mean)),
+            likeliest
+        );
+    }
+}
+
+pub fn run(command: BingoCommand) -> Result<()> {
+    match command {
+        BingoCommand::Play { rules, last, input } => {
+            let rules = win_rules(&rules)?;
+            let bingo = day4::parse_bingo(&read_input(input.as_ref())?)?;
+
+            let score = if last {
+                day4::last_winner(&bingo, &rules)?
+            } else {
+                day4::first_winner(&bingo, &rules)?
+            };
+
+            println!("{}", score.ok_or(Error::msg("no board ever wins"))?);
+        }
+        BingoCommand::Timeline {
+            rules,
+            kth,
+            format,
+            input,
+        } => {
+            let rules = win_rules(&rules)?;
+            let bingo = day4::parse_bingo(&read_input(input.as_ref())?)?;
+            let timeline = Timeline::new(&bingo, &rules)?;
+
+            if let Some(k) = kth {
+                if k == 0 {
+                    return Err(Error::msg("ranks are counted from 1"));
+                }
+                let win = timeline
+                    .kth(k)
+                    .ok_or_else(|| Error::msg(format!("fewer than {} boards win", k)))?;
+                println!("{}", win.score);
+                return Ok(());
+            }
+
+            match format {
+                Format::Text => print_timeline(&timeline),
+                Format::Json => {
+                    let boards: Vec<_> = (0..bingo.boards.len())
+                        .map(|board| timeline.board(board))
+                        .collect();
+                    println!("{}", serde_json::to_string_pretty(&boards)?);
+                }
+            }
+        }
+        BingoCommand::Replay { rules, at, input } => {
+            let rules = win_rules(&rules)?;
+            let bingo = day4::parse_bingo(&read_input(input.as_ref())?)?;
+            let mut replay = Replay::new(&bingo, &rules)?;
+
+            match at {
+                Some(round) => {
+                    replay.seek(round);
+                    let columns = terminal::size().map_or(80, |(columns, _)| columns as usize);
+                    for line in replay.render(columns, usize::MAX).0 {
+                        println!("{}", line);
+                    }
+                }
+                None => replay.run()?,
+            }
+        }
+        BingoCommand::Optimize {
+            board,
+            rules,
+            input,
+        } => {
+            let target = board
+                .checked_sub(1)
+                .ok_or(Error::msg("boards are counted from 1"))?;
+            let rules = win_rules(&rules)?;
+            let bingo = day4::parse_bingo(&read_input(input.as_ref())?)?;
+
+            match optimize::fastest_win(&bingo, &rules, target)? {
+                Some(plan) => {
+                    let draws: Vec<String> = plan.draws.iter().map(usize::to_string).collect();
+                    println!("{}", draws.join(","));
+                    println!(
+                        "board {} wins first after {} draws with score {}",
+                        board,
+                        plan.draws.len(),
+                        plan.score
+                    );
+                }
+                None => println!("board {} can never win first with these draws", board),
+            }
+        }
+        BingoCommand::Generate {
+            seed,
+            boards,
+            width,
+            height,
+            numbers,
+            draws,
+            unique,
+            rules,
+        } => {
+            let spec = Spec {
+                boards,
+                width,
+                height,
+                numbers,
+                draws,
+                unique_winners: unique,
+                rules: win_rules(&rules)?,
+            };
+
+            print!("{}", generate::generate(&spec, seed)?);
+        }
+        BingoCommand::Odds {
+            trials,
+            seed,
+            threads,
+            exact,
+            rules,
+            format,
+            input,
+        } => {
+            let rules = win_rules(&rules)?;
+            let bingo = day4::parse_bingo(&read_input(input.as_ref())?)?;
+
+            let odds = if exact {
+                odds::exact(&bingo, &rules)?
+            } else {
+                let threads = match threads {
+                    Some(threads) => threads,
+                    None => thread::available_parallelism()?.get(),
+                };
+                odds::simulate(&bingo, &rules, trials, seed, threads)?
+            };
+
+            match format {
+                Format::Text => print_odds(&odds),
+                Format::Json => println!("{}", serde_json::to_string_pretty(&odds)?),
+            }
+        }
+    }
+
+    Ok(())
+}
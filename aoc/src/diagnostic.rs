@@ -0,0 +1,106 @@
+use crate::{read_input, Format};
+use anyhow::Result;
+use aoc_core::bits::Bits;
+use aoc_core::day3::{self, BitStats, Criteria, Report, Trie};
+use clap::Subcommand;
+use num_bigint::BigUint;
+use std::path::PathBuf;
+
+#[derive(Subcommand)]
+pub enum DiagnosticCommand {
+    /// Rate life support under custom bit criteria
+    Ratings {
+        /// Criteria for the oxygen generator rating, as comma-separated
+        /// terms: most|least, tie=0|tie=1, msb|lsb, keep=K
+        #[arg(long, default_value = "most,tie=1,msb,keep=1")]
+        oxygen: Criteria,
+        /// Criteria for the CO2 scrubber rating
+        #[arg(long, default_value = "least,tie=0,msb,keep=1")]
+        co2: Criteria,
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+    /// Per-bit counts, entropy, ties and correlation between positions
+    Stats {
+        #[arg(short, long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+}
+
+fn print_stats(stats: &BitStats) {
+    println!("rows: {}", stats.rows);
+    println!("{:>4} {:>8} {:>8} {:>8}", "bit", "ones", "zeros", "entropy");
+    for bit in &stats.bits {
+        println!(
+            "{:>4} {:>8} {:>8} {:>8.4}{}",
+            bit.position,
+            bit.ones,
+            bit.zeros,
+            bit.entropy,
+            if bit.tie { "  tie" } else { "" }
+        );
+    }
+
+    if stats.ties.is_empty() {
+        println!("ties: none");
+    } else {
+        let ties: Vec<String> = stats.ties.iter().map(usize::to_string).collect();
+        println!("ties: {}", ties.join(", "));
+    }
+
+    println!("correlation:");
+    print!("{:>4}", "");
+    for position in 0..stats.bits.len() {
+        print!(" {:>6}", position);
+    }
+    println!();
+    for (position, row) in stats.correlation.iter().enumerate() {
+        print!("{:>4}", position);
+        for value in row {
+            match value {
+                Some(value) => print!(" {:>6.3}", value),
+                None => print!(" {:>6}", "-"),
+            }
+        }
+        println!();
+    }
+}
+
+fn rate(report: &Report, name: &str, criteria: &Criteria) -> Result<Vec<Bits>> {
+    let survivors = Trie::new(report, criteria.order).survivors(criteria)?;
+
+    println!("{} ({}):", name, criteria);
+    for value in &survivors {
+        println!("  {} ({})", value, value.to_biguint());
+    }
+
+    Ok(survivors)
+}
+
+pub fn run(command: DiagnosticCommand) -> Result<()> {
+    match command {
+        DiagnosticCommand::Ratings { oxygen, co2, input } => {
+            let report = day3::parse_report(&read_input(input.as_ref())?)?;
+            let oxygen = rate(&report, "oxygen generator", &oxygen)?;
+            let co2 = rate(&report, "CO2 scrubber", &co2)?;
+
+            if let ([oxygen], [co2]) = (&oxygen[..], &co2[..]) {
+                let life_support: BigUint = oxygen.to_biguint() * co2.to_biguint();
+                println!("life support: {}", life_support);
+            }
+        }
+        DiagnosticCommand::Stats { format, input } => {
+            let report = day3::parse_report(&read_input(input.as_ref())?)?;
+            let stats = BitStats::new(&report);
+
+            match format {
+                Format::Text => print_stats(&stats),
+                Format::Json => println!("{}", serde_json::to_string_pretty(&stats)?),
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use crate::read_input;
+use anyhow::{Error, Result};
+use aoc_core::day2::{self, Aimed, Model, Plain, Submarine, Waypoint};
+use clap::{Args, ValueEnum};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ModelArg {
+    /// `up` and `down` change the depth (part 1)
+    Plain,
+    /// `up` and `down` change the aim (part 2)
+    Aimed,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum TraceFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Args)]
+pub struct DiveArgs {
+    #[arg(short, long, value_enum, default_value_t = ModelArg::Aimed)]
+    model: ModelArg,
+    /// Print every intermediate position instead of the final answer
+    #[arg(short, long, value_enum)]
+    trace: Option<TraceFormat>,
+    /// Navigation script, reads stdin when omitted or `-`
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+}
+
+fn write_csv(out: &mut impl Write, waypoints: &[Waypoint]) -> Result<()> {
+    writeln!(out, "line,command,horizontal,depth,aim")?;
+    for waypoint in waypoints {
+        writeln!(
+            out,
+            "{},{},{},{},{}",
+            waypoint.line, waypoint.command, waypoint.horizontal, waypoint.depth, waypoint.aim
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn run(args: DiveArgs) -> Result<()> {
+    let model: &dyn Model = match args.model {
+        ModelArg::Plain => &Plain,
+        ModelArg::Aimed => &Aimed,
+    };
+    let steps = day2::parse_moves(&read_input(args.input.as_ref())?)?;
+
+    let format = match args.trace {
+        Some(format) => format,
+        None => {
+            let sub = day2::navigate(model, &steps)?;
+            println!("{}", sub.position_product()?);
+            return Ok(());
+        }
+    };
+
+    let waypoints = Submarine::default().trace(model, &steps)?;
+    let mut out = io::stdout().lock();
+    match format {
+        TraceFormat::Csv => write_csv(&mut out, &waypoints)?,
+        TraceFormat::Json => {
+            serde_json::to_writer_pretty(&mut out, &waypoints)?;
+            writeln!(out)?;
+        }
+    }
+
+    let breaches = day2::surface_breaches(&waypoints);
+
+    for breach in &breaches {
+        eprintln!("{}", breach);
+    }
+
+    if breaches.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::msg(format!(
+            "{} command(s) left the submarine above the surface",
+            breaches.len()
+        )))
+    }
+}
@@ -0,0 +1,194 @@
+use anyhow::{Error, Result};
+use aoc_core::answers::{self, Answers, Verdict};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+mod bingo;
+mod diagnostic;
+mod dive;
+mod replay;
+mod sonar;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2021 solutions")]
+struct Cli {
+    /// Report arithmetic overflow as an error instead of wrapping or panicking
+    #[arg(long, global = true)]
+    checked: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solver for one puzzle, both parts unless one is given
+    Run {
+        day: u8,
+        part: Option<u8>,
+        /// Puzzle input file, reads stdin when omitted or `-`
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        /// Report parse and solve times on stderr
+        #[arg(short, long)]
+        time: bool,
+    },
+    /// Check every solver against a local answers file
+    Verify {
+        /// Only verify this day
+        day: Option<u8>,
+        #[arg(short, long, default_value = "answers.toml")]
+        answers: PathBuf,
+        /// Directory holding the `dayN.txt` puzzle inputs
+        #[arg(long, default_value = "inputs")]
+        inputs: PathBuf,
+    },
+    /// Sonar sweep depth log tools (day 1)
+    Sonar {
+        #[command(subcommand)]
+        command: sonar::SonarCommand,
+    },
+    /// Dive simulator for submarine scripts (day 2)
+    Dive(dive::DiveArgs),
+    /// Binary diagnostic report tools (day 3)
+    Diagnostic {
+        #[command(subcommand)]
+        command: diagnostic::DiagnosticCommand,
+    },
+    /// Bingo tools with custom win rules (day 4)
+    Bingo {
+        #[command(subcommand)]
+        command: bingo::BingoCommand,
+    },
+    /// List the registered solvers
+    List,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+fn open_input(path: Option<&PathBuf>) -> Result<Box<dyn BufRead>> {
+    match path {
+        Some(path) if path.as_os_str() != "-" => Ok(Box::new(BufReader::new(File::open(path)?))),
+        _ => Ok(Box::new(io::stdin().lock())),
+    }
+}
+
+fn read_input(path: Option<&PathBuf>) -> Result<String> {
+    match path {
+        Some(path) if path.as_os_str() != "-" => Ok(fs::read_to_string(path)?),
+        _ => {
+            let mut cache = String::new();
+            io::stdin().lock().read_to_string(&mut cache)?;
+            Ok(cache)
+        }
+    }
+}
+
+fn verify(day: Option<u8>, answers: &Path, inputs: &Path) -> Result<bool> {
+    let answers = Answers::load(answers)?;
+    let mut all_passed = true;
+
+    for puzzle in aoc_core::puzzles() {
+        if day.is_some_and(|day| day != puzzle.day()) {
+            continue;
+        }
+
+        let path = inputs.join(format!("day{}.txt", puzzle.day()));
+        let input = match fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(_) => {
+                println!("day {}: no input at {}", puzzle.day(), path.display());
+                continue;
+            }
+        };
+        let hash = answers::input_hash(&input);
+
+        let run = match puzzle.run(&input, &[1, 2]) {
+            Ok(run) => run,
+            Err(e) => {
+                println!("day {}: error: {}", puzzle.day(), e);
+                all_passed = false;
+                continue;
+            }
+        };
+
+        for part_run in run.parts {
+            let verdict = answers.check(puzzle.day(), part_run.part, &hash, &part_run.answer);
+
+            print!("day {} part {}: ", puzzle.day(), part_run.part);
+            match verdict {
+                Verdict::Pass => println!("pass"),
+                Verdict::Fail { expected } => {
+                    all_passed = false;
+                    println!("FAIL (got {}, expected {})", part_run.answer, expected);
+                }
+                Verdict::Unknown => {
+                    println!("unknown (got {}, input {})", part_run.answer, hash)
+                }
+            }
+        }
+    }
+
+    Ok(all_passed)
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    aoc_core::arith::set_checked(cli.checked);
+
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            time,
+        } => {
+            let puzzle = aoc_core::puzzle(day)
+                .ok_or_else(|| Error::msg(format!("no solver registered for day {}", day)))?;
+            let input = read_input(input.as_ref())?;
+            let parts = part.map_or(vec![1, 2], |part| vec![part]);
+            let run = puzzle.run(&input, &parts)?;
+
+            if time {
+                eprintln!("day {} parse: {:?}", day, run.parse_time);
+            }
+
+            for part_run in run.parts {
+                if parts.len() > 1 {
+                    println!("part {}: {}", part_run.part, part_run.answer);
+                } else {
+                    println!("{}", part_run.answer);
+                }
+
+                if time {
+                    eprintln!("day {} part {}: {:?}", day, part_run.part, part_run.elapsed);
+                }
+            }
+        }
+        Command::Verify {
+            day,
+            answers,
+            inputs,
+        } => {
+            if !verify(day, &answers, &inputs)? {
+                return Err(Error::msg("some answers did not match"));
+            }
+        }
+        Command::Sonar { command } => sonar::run(command)?,
+        Command::Dive(args) => dive::run(args)?,
+        Command::Diagnostic { command } => diagnostic::run(command)?,
+        Command::Bingo { command } => bingo::run(command)?,
+        Command::List => {
+            for puzzle in aoc_core::puzzles() {
+                println!("day {}: {}", puzzle.day(), puzzle.name());
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,309 @@
+//! Step-by-step bingo replay in the terminal.
+
+use anyhow::Result;
+use aoc_core::day4::{Bingo, Game, Timeline, WinRule};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::style::Stylize;
+use crossterm::{cursor, execute, queue, style, terminal};
+use std::io::{self, Write};
+
+const HELP: &str =
+    "→/space step  ← back  n/p next/previous win  home/end  pgup/pgdn scroll  q quit";
+
+pub struct Replay<'a> {
+    bingo: &'a Bingo,
+    rules: &'a [WinRule],
+    timeline: Timeline,
+    game: Game<'a>,
+    /// First board shown on screen.
+    offset: usize,
+}
+
+impl<'a> Replay<'a> {
+    pub fn new(bingo: &'a Bingo, rules: &'a [WinRule]) -> Result<Self> {
+        Ok(Replay {
+            bingo,
+            rules,
+            timeline: Timeline::new(bingo, rules)?,
+            game: Game::new(bingo, rules)?,
+            offset: 0,
+        })
+    }
+
+    /// Replays the game up to `round` draws, from the start when going back.
+    pub fn seek(&mut self, round: usize) {
+        let round = round.min(self.bingo.rounds.len());
+
+        if round < self.game.round() {
+            self.game = Game::new(self.bingo, self.rules).expect("rules compiled for the timeline");
+        }
+
+        while self.game.round() < round {
+            self.game.draw(self.bingo.rounds[self.game.round()]);
+        }
+    }
+
+    fn next_win(&self) -> Option<usize> {
+        self.timeline
+            .ranked()
+            .map(|win| win.round)
+            .find(|&round| round > self.game.round())
+    }
+
+    fn previous_win(&self) -> Option<usize> {
+        self.timeline
+            .ranked()
+            .map(|win| win.round)
+            .take_while(|&round| round < self.game.round())
+            .last()
+    }
+
+    fn status(&self) -> String {
+        let round = self.game.round();
+        let mut status = format!("round {}/{}", round, self.bingo.rounds.len());
+
+        if round > 0 {
+            status += &format!(", drew {}", self.bingo.rounds[round - 1]);
+        }
+
+        for win in self.timeline.ranked().filter(|win| win.round == round) {
+            status += &format!(", board {} wins with {}", win.board + 1, win.score);
+        }
+
+        status
+    }
+
+    fn title(&self, board: usize, won: bool) -> String {
+        match self.timeline.board(board) {
+            Some(win) if won => format!("board {} ✓ {}", board + 1, win.score),
+            _ => format!("board {}", board + 1),
+        }
+    }
+
+    fn render_board(&self, board: usize, cell_width: usize) -> Vec<String> {
+        let width = self.bingo.boards[board].width();
+        let numbers: Vec<usize> = self.bingo.boards[board].numbers().collect();
+        let winning = self.game.winning_cells(board).unwrap_or(&[]);
+        let mut lines = vec![self.title(board, self.game.has_won(board))];
+
+        for row in numbers.chunks(width).enumerate() {
+            let (y, row) = row;
+            let mut line = String::new();
+
+            for (x, number) in row.iter().enumerate() {
+                let cell = y * width + x;
+                let text = format!("{:>1$}", number, cell_width);
+
+                let styled = if winning.contains(&cell) {
+                    text.black().on_yellow().bold().to_string()
+                } else if self.game.is_marked(board, cell) {
+                    text.green().bold().to_string()
+                } else {
+                    text.dark_grey().to_string()
+                };
+                line += &styled;
+                line.push(' ');
+            }
+
+            lines.push(line);
+        }
+
+        lines
+    }
+
+    /// Lines of one screen, `columns` wide and at most `rows` high, and how
+    /// many boards fit on it.
+    pub fn render(&self, columns: usize, rows: usize) -> (Vec<String>, usize) {
+        let boards = &self.bingo.boards;
+        let cell_width = boards
+            .iter()
+            .flat_map(|board| board.numbers())
+            .max()
+            .map_or(1, |number| number.to_string().len());
+        let board_width = boards
+            .iter()
+            .enumerate()
+            .map(|(i, board)| {
+                let title = self.title(i, true).chars().count();
+                title.max(board.width() * (cell_width + 1))
+            })
+            .max()
+            .unwrap_or(0);
+
+        let mut lines = vec![self.status(), String::new()];
+        let per_row = (columns / (board_width + 2)).max(1);
+        let mut board = self.offset;
+
+        while board < boards.len() {
+            let group: Vec<Vec<String>> = (board..boards.len().min(board + per_row))
+                .map(|board| self.render_board(board, cell_width))
+                .collect();
+            let mut height = group.iter().map(Vec::len).max().unwrap_or(0);
+
+            // Always show the first group, cut to the screen if it is too tall.
+            let clipped = lines.len() + height + 2 > rows;
+            if clipped && board > self.offset {
+                break;
+            }
+            if clipped {
+                height = height.min(rows.saturating_sub(lines.len() + 1));
+            }
+
+            for i in 0..height {
+                let line = group
+                    .iter()
+                    .map(|board_lines| match board_lines.get(i) {
+                        // Styling adds invisible bytes, so pad by the plain width.
+                        Some(line) => {
+                            let visible = console_width(line);
+                            let padding = (board_width + 2).saturating_sub(visible);
+                            format!("{}{}", line, " ".repeat(padding))
+                        }
+                        None => " ".repeat(board_width + 2),
+                    })
+                    .collect::<String>();
+                lines.push(line.trim_end().to_string());
+            }
+            board += group.len();
+            if clipped {
+                break;
+            }
+            lines.push(String::new());
+        }
+
+        if board < boards.len() {
+            lines.push(format!(
+                "boards {}-{} of {}",
+                self.offset + 1,
+                board,
+                boards.len()
+            ));
+        }
+
+        (lines, board - self.offset)
+    }
+
+    /// Runs the interactive replay until the user quits.
+    pub fn run(&mut self) -> Result<()> {
+        let mut out = io::stdout();
+        terminal::enable_raw_mode()?;
+        execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+        let result = self.event_loop(&mut out);
+
+        execute!(out, cursor::Show, terminal::LeaveAlternateScreen)?;
+        terminal::disable_raw_mode()?;
+        result
+    }
+
+    fn event_loop(&mut self, out: &mut impl Write) -> Result<()> {
+        loop {
+            let (columns, rows) = terminal::size()?;
+            let (columns, rows) = (columns as usize, rows as usize);
+
+            let (lines, page) = self.render(columns, rows.saturating_sub(1));
+
+            queue!(out, terminal::Clear(terminal::ClearType::All))?;
+            for (y, line) in lines.iter().enumerate() {
+                queue!(out, cursor::MoveTo(0, y as u16), style::Print(line))?;
+            }
+            queue!(
+                out,
+                cursor::MoveTo(0, rows.saturating_sub(1) as u16),
+                style::Print(HELP.dark_grey())
+            )?;
+            out.flush()?;
+
+            let key = match event::read()? {
+                Event::Key(key) if key.kind != KeyEventKind::Release => key,
+                _ => continue,
+            };
+
+            let round = self.game.round();
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Right | KeyCode::Char(' ') | KeyCode::Char('l') => self.seek(round + 1),
+                KeyCode::Left | KeyCode::Char('h') => self.seek(round.saturating_sub(1)),
+                KeyCode::Char('n') => {
+                    if let Some(round) = self.next_win() {
+                        self.seek(round);
+                    }
+                }
+                KeyCode::Char('p') => {
+                    if let Some(round) = self.previous_win() {
+                        self.seek(round);
+                    }
+                }
+                KeyCode::Home => self.seek(0),
+                KeyCode::End => self.seek(self.bingo.rounds.len()),
+                KeyCode::PageDown if self.offset + page < self.bingo.boards.len() => {
+                    self.offset += page.max(1)
+                }
+                KeyCode::PageUp => self.offset = self.offset.saturating_sub(page.max(1)),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Printed width of `line`, skipping ANSI escape sequences.
+fn console_width(line: &str) -> usize {
+    let mut width = 0;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            width += 1;
+        }
+    }
+
+    width
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::day4::parse_bingo;
+
+    #[test]
+    fn test_render() {
+        let bingo = parse_bingo(include_str!("../../aoc-core/fixtures/day4.txt")).unwrap();
+        let rules = [WinRule::Lines];
+        let mut replay = Replay::new(&bingo, &rules).unwrap();
+
+        replay.seek(12);
+        let (lines, page) = replay.render(120, usize::MAX);
+
+        assert_eq!(page, 3);
+        assert_eq!(lines[0], "round 12/27, drew 24, board 3 wins with 4512");
+        assert!(lines[2].starts_with("board 1 ") && lines[2].ends_with("board 3 ✓ 4512"));
+        assert_eq!(console_width(&lines[3]), 2 * 17 + "14 21 17 24  4".len());
+        // First rows: 22 unmarked, 17 marked, and the winning row of board 3.
+        assert!(lines[3].contains(&"22".dark_grey().to_string()));
+        assert!(lines[3].contains(&"17".green().bold().to_string()));
+        assert!(lines[3].contains(&"14".black().on_yellow().bold().to_string()));
+        assert!(!lines[4].contains(&"10".black().on_yellow().bold().to_string()));
+
+        replay.seek(11);
+        assert_eq!(replay.render(120, usize::MAX).0[0], "round 11/27, drew 21");
+    }
+
+    #[test]
+    fn test_render_short_screen() {
+        let bingo = parse_bingo(include_str!("../../aoc-core/fixtures/day4.txt")).unwrap();
+        let rules = [WinRule::Lines];
+        let replay = Replay::new(&bingo, &rules).unwrap();
+
+        let (lines, page) = replay.render(20, 5);
+        assert_eq!(page, 1);
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[2], "board 1");
+        assert_eq!(lines[4], "boards 1-1 of 3");
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{open_input, Format};
+use anyhow::{Error, Result};
+use aoc_core::day1::{self, DepthReport};
+use aoc_core::window::{self, Aggregate};
+use clap::Subcommand;
+use std::path::PathBuf;
+
+#[derive(Subcommand)]
+pub enum SonarCommand {
+    /// Count increases of a sliding-window aggregate over the depth log
+    Increases {
+        /// Number of measurements per window
+        #[arg(short, long, default_value_t = 3)]
+        window: usize,
+        /// One of sum, min, max or mean
+        #[arg(short, long, default_value = "sum")]
+        aggregate: Aggregate,
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+    /// Summarize the depth log in a single streaming pass
+    Report {
+        /// Width of the delta histogram buckets
+        #[arg(short, long, default_value_t = 1)]
+        bucket: usize,
+        #[arg(short, long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+}
+
+fn print_report(report: &DepthReport) {
+    println!("measurements: {}", report.measurements);
+    println!("increases: {}", report.increases);
+    println!("decreases: {}", report.decreases);
+    println!("plateaus: {}", report.plateaus);
+    println!("longest increasing run: {}", report.longest_increasing_run);
+    println!("max drop: {}", report.max_drop);
+    println!("delta histogram:");
+    for (delta, count) in &report.deltas {
+        println!("  {:>6}: {}", delta, count);
+    }
+}
+
+pub fn run(command: SonarCommand) -> Result<()> {
+    match command {
+        SonarCommand::Increases {
+            window,
+            aggregate,
+            input,
+        } => {
+            if window == 0 {
+                return Err(Error::msg("window width must be at least 1"));
+            }
+
+            let depths = day1::read_depths(open_input(input.as_ref())?);
+            let increases = itertools::process_results(depths, |depths| {
+                window::increases(depths, window, aggregate)
+            })?;
+
+            println!("{}", increases);
+        }
+        SonarCommand::Report {
+            bucket,
+            format,
+            input,
+        } => {
+            let mut report = DepthReport::new(bucket);
+
+            for depth in day1::read_depths(open_input(input.as_ref())?) {
+                report.push(depth?);
+            }
+
+            match format {
+                Format::Text => print_report(&report),
+                Format::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+            }
+        }
+    }
+
+    Ok(())
+}